@@ -0,0 +1,93 @@
+use anchor_lang::error_code;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Invalid amount")]
+    InvalidAmount,
+    #[msg("Auction end time must be in the future")]
+    InvalidEndTime,
+    #[msg("Auction has ended")]
+    AuctionEnded,
+    #[msg("Auction has not ended yet")]
+    AuctionNotEnded,
+    #[msg("Bid is below the reserve price or minimum increment")]
+    BidTooLow,
+    #[msg("Outbid accounts are missing or do not match the highest bid")]
+    InvalidPreviousBid,
+    #[msg("Winning bid accounts are missing or do not match the highest bid")]
+    InvalidWinningBid,
+    #[msg("A required token account was not provided")]
+    MissingTokenAccount,
+    #[msg("Arbitrated escrows settle through release or the arbiter")]
+    ArbitratedEscrow,
+    #[msg("Escrow has no arbiter")]
+    NotArbitrated,
+    #[msg("Escrow is not in the expected state")]
+    InvalidEscrowStatus,
+    #[msg("Maker share exceeds 10000 bps")]
+    InvalidBps,
+    #[msg("Signer is not the escrow arbiter")]
+    NotArbiter,
+    #[msg("Signer is neither the maker nor the taker")]
+    NotParticipant,
+    #[msg("Taker does not match the escrow")]
+    InvalidTaker,
+    #[msg("Milestone escrows settle through approve_milestone and refund_milestones")]
+    MilestoneEscrow,
+    #[msg("Escrow has no milestones")]
+    NotMilestoneEscrow,
+    #[msg("Milestones must be non-empty, at most 10, and each amount above zero")]
+    InvalidMilestones,
+    #[msg("Milestone index out of range")]
+    InvalidMilestoneIndex,
+    #[msg("Milestone is not pending")]
+    MilestoneNotPending,
+    #[msg("Deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("Deadline has not passed yet")]
+    DeadlineNotReached,
+    #[msg("Payee does not match the escrow")]
+    InvalidPayee,
+    #[msg("Remaining accounts are missing or do not match an escrow group")]
+    InvalidRemainingAccounts,
+    #[msg("Total mint_b spent exceeds the taker's limit")]
+    TotalReceiveExceeded,
+    #[msg("Escrow is on the other side of the book")]
+    WrongSide,
+    #[msg("Mint does not match the escrow")]
+    MintMismatch,
+    #[msg("Cannot top up and withdraw in the same update")]
+    ConflictingUpdate,
+    #[msg("Withdrawal would empty the vault, use refund instead")]
+    WithdrawExceedsVault,
+    #[msg("Vesting needs a positive duration and a cliff within it")]
+    InvalidSchedule,
+    #[msg("Nothing has vested since the last claim")]
+    NothingToClaim,
+    #[msg("Grant is not revocable")]
+    NotRevocable,
+    #[msg("Oracle escrows are released by attestation or expired after the deadline")]
+    OracleEscrow,
+    #[msg("Escrow is not an oracle escrow")]
+    NotOracleEscrow,
+    #[msg("Deadline has passed")]
+    DeadlinePassed,
+    #[msg("No ed25519 attestation precedes this instruction")]
+    MissingAttestation,
+    #[msg("Attestation is malformed, not signed by the oracle, or for another escrow")]
+    InvalidAttestation,
+    #[msg("Maker does not match the escrow")]
+    InvalidMaker,
+    #[msg("Taker does not hold enough mint_b to pay the escrow")]
+    InsufficientTakerBalance,
+    #[msg("Arbiter fee exceeds 1000 bps")]
+    ArbiterFeeTooHigh,
+    #[msg("Arbiter cannot be the maker or the taker")]
+    InvalidArbiter,
+    #[msg("Escrow terms are worse than the taker accepted")]
+    TermsChanged,
+    #[msg("Plain asks and bids need their pair's order book")]
+    MissingOrderBook,
+    #[msg("Overflow")]
+    Overflow,
+}
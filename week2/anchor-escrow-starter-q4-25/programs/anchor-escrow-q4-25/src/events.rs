@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MakeEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub seed: u64,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub deposit: u64,
+    pub receive: u64,
+}
+
+#[event]
+pub struct TakeEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    // mint_a paid out of the vault to the taker
+    pub amount_a: u64,
+    // mint_b paid by the taker to the maker
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct EscrowUpdated {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub old_receive: u64,
+    pub new_receive: u64,
+    pub old_deposit: u64,
+    pub new_deposit: u64,
+}
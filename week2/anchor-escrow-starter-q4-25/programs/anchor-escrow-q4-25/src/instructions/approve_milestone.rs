@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{errors::EscrowError, Escrow, MilestoneStatus};
+
+#[derive(Accounts)]
+pub struct ApproveMilestone<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub payee: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = !escrow.milestones.is_empty() @ EscrowError::NotMilestoneEscrow,
+        constraint = escrow.taker == Some(payee.key()) @ EscrowError::InvalidPayee,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = payee,
+        associated_token::token_program = token_program
+    )]
+    pub payee_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveMilestone<'info> {
+    pub fn approve_milestone(&mut self, index: u8) -> Result<()> {
+        let milestone = self
+            .escrow
+            .milestones
+            .get(index as usize)
+            .copied()
+            .ok_or(EscrowError::InvalidMilestoneIndex)?;
+        require!(
+            milestone.status == MilestoneStatus::Pending,
+            EscrowError::MilestoneNotPending
+        );
+
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: self.vault.to_account_info(),
+                to: self.payee_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, milestone.amount, self.mint_a.decimals)?;
+
+        self.escrow.milestones[index as usize].status = MilestoneStatus::Released;
+
+        Ok(())
+    }
+}
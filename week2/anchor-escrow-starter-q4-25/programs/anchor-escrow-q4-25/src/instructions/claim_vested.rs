@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Vesting};
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub beneficiary: Signer<'info>,
+    // Gets the rent back once the grant is fully claimed
+    #[account(mut)]
+    pub grantor: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        has_one = grantor,
+        has_one = beneficiary,
+        has_one = mint,
+        seeds = [b"vesting", grantor.key().as_ref(), vesting.seed.to_le_bytes().as_ref()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = beneficiary,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn claim_vested(&mut self) -> Result<()> {
+        let claimable = self.vesting.claimable(Clock::get()?.unix_timestamp)?;
+        require!(claimable > 0, EscrowError::NothingToClaim);
+        self.vesting.claimed = self
+            .vesting
+            .claimed
+            .checked_add(claimable)
+            .ok_or(EscrowError::Overflow)?;
+        let fully_claimed = self.vesting.claimed >= self.vesting.total;
+        // The last tranche empties the vault, tokens sent to it outside the
+        // grant included, so it can always be closed
+        let amount = if fully_claimed {
+            self.vault.amount
+        } else {
+            claimable
+        };
+
+        let grantor_key = self.grantor.key();
+        let seed = self.vesting.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vesting",
+            grantor_key.as_ref(),
+            seed.as_ref(),
+            &[self.vesting.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint.to_account_info(),
+                from: self.vault.to_account_info(),
+                to: self.beneficiary_ata.to_account_info(),
+                authority: self.vesting.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, amount, self.mint.decimals)?;
+
+        if !fully_claimed {
+            return Ok(());
+        }
+
+        // Fully vested and claimed: the grant is done
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.grantor.to_account_info(),
+                authority: self.vesting.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)?;
+        self.vesting.close(self.grantor.to_account_info())
+    }
+}
@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Escrow, OrderBook, OrderSide};
+
+#[derive(Accounts)]
+pub struct FillBid<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = taker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = escrow.side == OrderSide::Bid @ EscrowError::WrongSide,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FillBid<'info> {
+    pub fn check_terms(&self, expected_receive: u64, min_amount_b: u64) -> Result<()> {
+        self.escrow
+            .check_terms(self.vault.amount, expected_receive, min_amount_b)
+    }
+
+    pub fn remove_from_book(&mut self) -> Result<()> {
+        self.order_book.remove(OrderSide::Bid, &self.escrow.key());
+        Ok(())
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: self.taker_ata_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.taker.to_account_info(),
+            },
+        );
+
+        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_a.decimals)
+    }
+
+    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: self.vault.to_account_info(),
+                to: self.taker_ata_b.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault.amount, self.mint_b.decimals)?;
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.taker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
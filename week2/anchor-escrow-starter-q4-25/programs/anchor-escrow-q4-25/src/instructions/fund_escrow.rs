@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct FundEscrow<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_b,
+        constraint = escrow.arbiter.is_some() @ EscrowError::NotArbitrated,
+        constraint = escrow.arbiter != Some(taker.key()) @ EscrowError::InvalidArbiter,
+        constraint = escrow.status == EscrowStatus::Open @ EscrowError::InvalidEscrowStatus,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FundEscrow<'info> {
+    pub fn fund(&mut self) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: self.taker_ata_b.to_account_info(),
+                to: self.vault_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+            },
+        );
+
+        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)?;
+
+        self.escrow.taker = Some(self.taker.key());
+        self.escrow.status = EscrowStatus::Funded;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,8 @@
 use anchor_lang::prelude::*;
 
-use crate::Escrow;
+use crate::{
+    errors::EscrowError, events::MakeEvent, BookEntry, Escrow, EscrowStatus, OrderBook, OrderSide,
+};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
@@ -42,6 +44,14 @@ pub struct Make<'info> {
         associated_token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        space = OrderBook::DISCRIMINATOR.len() + OrderBook::INIT_SPACE,
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -49,19 +59,56 @@ pub struct Make<'info> {
 }
 
 impl<'info> Make<'info> {
-    pub fn init_escrow(&mut self, seed: u64, receive: u64, bumps: &MakeBumps) -> Result<()> {
+    pub fn init_escrow(
+        &mut self,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        bumps: &MakeBumps,
+    ) -> Result<()> {
+        require!(receive > 0, EscrowError::InvalidAmount);
+
         self.escrow.set_inner(Escrow {
             seed,
             maker: self.maker.key(),
             mint_a: self.mint_a.key(),
             mint_b: self.mint_b.key(),
             receive,
+            side: OrderSide::Ask,
+            arbiter: None,
+            arbiter_fee_bps: 0,
+            taker: None,
+            oracle: None,
+            status: EscrowStatus::Open,
+            milestones: vec![],
+            deadline: 0,
             bump: bumps.escrow,
         });
+
+        emit!(MakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            seed,
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit,
+            receive,
+        });
         Ok(())
     }
 
+    pub fn add_to_book(&mut self, deposit: u64, bumps: &MakeBumps) -> Result<()> {
+        self.order_book.mint_a = self.mint_a.key();
+        self.order_book.mint_b = self.mint_b.key();
+        self.order_book.bump = bumps.order_book;
+
+        let entry = BookEntry::new(self.escrow.key(), &self.escrow, deposit);
+        self.order_book.insert(OrderSide::Ask, entry)
+    }
+
     pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidAmount);
+
         let tranfer_accounts = TransferChecked {
             from: self.maker_ata_a.to_account_info(),
             mint: self.mint_a.to_account_info(),
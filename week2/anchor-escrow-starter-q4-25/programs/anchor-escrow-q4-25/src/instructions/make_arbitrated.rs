@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus, OrderSide, MAX_ARBITER_FEE_BPS};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeArbitrated<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    /// CHECK: only stored on the escrow, must sign `resolve_dispute`
+    pub arbiter: UncheckedAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Escrow::DISCRIMINATOR.len() + Escrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeArbitrated<'info> {
+    pub fn init_escrow(
+        &mut self,
+        seed: u64,
+        receive: u64,
+        arbiter_fee_bps: u16,
+        bumps: &MakeArbitratedBumps,
+    ) -> Result<()> {
+        require!(receive > 0, EscrowError::InvalidAmount);
+        require!(
+            arbiter_fee_bps <= MAX_ARBITER_FEE_BPS,
+            EscrowError::ArbiterFeeTooHigh
+        );
+        require_keys_neq!(
+            self.arbiter.key(),
+            self.maker.key(),
+            EscrowError::InvalidArbiter
+        );
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            receive,
+            side: OrderSide::Ask,
+            arbiter: Some(self.arbiter.key()),
+            arbiter_fee_bps,
+            taker: None,
+            oracle: None,
+            status: EscrowStatus::Open,
+            milestones: vec![],
+            deadline: 0,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Auction};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeAuction<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"auction", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Auction::DISCRIMINATOR.len() + Auction::INIT_SPACE,
+        bump
+    )]
+    pub auction: Account<'info, Auction>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeAuction<'info> {
+    pub fn init_auction(
+        &mut self,
+        seed: u64,
+        reserve_price: u64,
+        min_increment: u64,
+        end_time: i64,
+        extension_window: i64,
+        bumps: &MakeAuctionBumps,
+    ) -> Result<()> {
+        require!(
+            end_time > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidEndTime
+        );
+        require!(min_increment > 0, EscrowError::InvalidAmount);
+        require!(extension_window >= 0, EscrowError::InvalidEndTime);
+
+        self.auction.set_inner(Auction {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            reserve_price,
+            min_increment,
+            end_time,
+            extension_window,
+            highest_bidder: None,
+            highest_bid: 0,
+            bump: bumps.auction,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
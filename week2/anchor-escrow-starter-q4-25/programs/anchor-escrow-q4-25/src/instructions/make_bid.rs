@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, BookEntry, Escrow, EscrowStatus, OrderBook, OrderSide};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeBid<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Escrow::DISCRIMINATOR.len() + Escrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        space = OrderBook::DISCRIMINATOR.len() + OrderBook::INIT_SPACE,
+        bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeBid<'info> {
+    pub fn init_escrow(&mut self, seed: u64, receive: u64, bumps: &MakeBidBumps) -> Result<()> {
+        require!(receive > 0, EscrowError::InvalidAmount);
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            receive,
+            side: OrderSide::Bid,
+            arbiter: None,
+            arbiter_fee_bps: 0,
+            taker: None,
+            oracle: None,
+            status: EscrowStatus::Open,
+            milestones: vec![],
+            deadline: 0,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+
+    pub fn add_to_book(&mut self, deposit: u64, bumps: &MakeBidBumps) -> Result<()> {
+        self.order_book.mint_a = self.mint_a.key();
+        self.order_book.mint_b = self.mint_b.key();
+        self.order_book.bump = bumps.order_book;
+
+        let entry = BookEntry::new(self.escrow.key(), &self.escrow, deposit);
+        self.order_book.insert(OrderSide::Bid, entry)
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_b.decimals)
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::EscrowError, Escrow, EscrowStatus, Milestone, MilestoneStatus, OrderSide,
+    MAX_MILESTONES,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeMilestone<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    /// CHECK: only stored on the escrow, receives the approved tranches
+    pub payee: UncheckedAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Escrow::DISCRIMINATOR.len() + Escrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeMilestone<'info> {
+    pub fn init_escrow(
+        &mut self,
+        seed: u64,
+        amounts: Vec<u64>,
+        deadline: i64,
+        bumps: &MakeMilestoneBumps,
+    ) -> Result<()> {
+        require!(
+            !amounts.is_empty()
+                && amounts.len() <= MAX_MILESTONES
+                && amounts.iter().all(|amount| *amount > 0),
+            EscrowError::InvalidMilestones
+        );
+        require!(
+            deadline > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDeadline
+        );
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            // Milestone escrows pay out in mint_a only
+            mint_b: Pubkey::default(),
+            receive: 0,
+            side: OrderSide::Ask,
+            arbiter: None,
+            arbiter_fee_bps: 0,
+            taker: Some(self.payee.key()),
+            oracle: None,
+            status: EscrowStatus::Open,
+            milestones: amounts
+                .into_iter()
+                .map(|amount| Milestone {
+                    amount,
+                    status: MilestoneStatus::Pending,
+                })
+                .collect(),
+            deadline,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amounts: &[u64]) -> Result<()> {
+        let total = amounts
+            .iter()
+            .try_fold(0u64, |total, amount| total.checked_add(*amount))
+            .ok_or(EscrowError::Overflow)?;
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, total, self.mint_a.decimals)
+    }
+}
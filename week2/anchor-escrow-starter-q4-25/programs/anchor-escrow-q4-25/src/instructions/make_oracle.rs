@@ -0,0 +1,92 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus, OrderSide};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeOracle<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    /// CHECK: only stored on the escrow, receives the deposit on a valid attestation
+    pub taker: UncheckedAccount<'info>,
+    /// CHECK: only stored on the escrow, its ed25519 signature releases the deposit
+    pub oracle: UncheckedAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        seeds = [b"escrow", maker.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Escrow::DISCRIMINATOR.len() + Escrow::INIT_SPACE,
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeOracle<'info> {
+    pub fn init_escrow(&mut self, seed: u64, deadline: i64, bumps: &MakeOracleBumps) -> Result<()> {
+        require!(
+            deadline > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDeadline
+        );
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            // Oracle escrows pay out in mint_a only
+            mint_b: Pubkey::default(),
+            receive: 0,
+            side: OrderSide::Ask,
+            arbiter: None,
+            arbiter_fee_bps: 0,
+            taker: Some(self.taker.key()),
+            oracle: Some(self.oracle.key()),
+            status: EscrowStatus::Open,
+            milestones: vec![],
+            deadline,
+            bump: bumps.escrow,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        require!(deposit > 0, EscrowError::InvalidAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
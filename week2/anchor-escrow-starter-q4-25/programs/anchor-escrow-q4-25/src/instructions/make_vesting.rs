@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Vesting};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MakeVestingArgs {
+    pub amount: u64,
+    pub start: i64,
+    pub cliff: i64,
+    pub duration: i64,
+    pub revocable: bool,
+}
+
+#[derive(Accounts)]
+#[instruction(seed: u64)]
+pub struct MakeVesting<'info> {
+    #[account(mut)]
+    pub grantor: Signer<'info>,
+    /// CHECK: only stored on the grant, must sign `claim_vested`
+    pub beneficiary: UncheckedAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = grantor,
+        associated_token::token_program = token_program
+    )]
+    pub grantor_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = grantor,
+        seeds = [b"vesting", grantor.key().as_ref(), seed.to_le_bytes().as_ref()],
+        space = Vesting::DISCRIMINATOR.len() + Vesting::INIT_SPACE,
+        bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        init,
+        payer = grantor,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeVesting<'info> {
+    pub fn init_vesting(
+        &mut self,
+        seed: u64,
+        args: &MakeVestingArgs,
+        bumps: &MakeVestingBumps,
+    ) -> Result<()> {
+        require!(
+            args.duration > 0 && (0..=args.duration).contains(&args.cliff),
+            EscrowError::InvalidSchedule
+        );
+
+        self.vesting.set_inner(Vesting {
+            seed,
+            grantor: self.grantor.key(),
+            beneficiary: self.beneficiary.key(),
+            mint: self.mint.key(),
+            total: args.amount,
+            claimed: 0,
+            start: args.start,
+            cliff: args.cliff,
+            duration: args.duration,
+            revocable: args.revocable,
+            bump: bumps.vesting,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.grantor_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.grantor.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
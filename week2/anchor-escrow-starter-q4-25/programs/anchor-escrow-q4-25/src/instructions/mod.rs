@@ -6,3 +6,69 @@ pub use refund::*;
 
 pub mod take;
 pub use take::*;
+
+pub mod make_auction;
+pub use make_auction::*;
+
+pub mod place_bid;
+pub use place_bid::*;
+
+pub mod settle;
+pub use settle::*;
+
+pub mod make_arbitrated;
+pub use make_arbitrated::*;
+
+pub mod fund_escrow;
+pub use fund_escrow::*;
+
+pub mod raise_dispute;
+pub use raise_dispute::*;
+
+pub mod release;
+pub use release::*;
+
+pub mod resolve_dispute;
+pub use resolve_dispute::*;
+
+pub mod make_milestone;
+pub use make_milestone::*;
+
+pub mod approve_milestone;
+pub use approve_milestone::*;
+
+pub mod refund_milestones;
+pub use refund_milestones::*;
+
+pub mod take_many;
+pub use take_many::*;
+
+pub mod make_bid;
+pub use make_bid::*;
+
+pub mod fill_bid;
+pub use fill_bid::*;
+
+pub mod cancel_bid;
+pub use cancel_bid::*;
+
+pub mod update_escrow;
+pub use update_escrow::*;
+
+pub mod make_vesting;
+pub use make_vesting::*;
+
+pub mod claim_vested;
+pub use claim_vested::*;
+
+pub mod revoke;
+pub use revoke::*;
+
+pub mod make_oracle;
+pub use make_oracle::*;
+
+pub mod release_attested;
+pub use release_attested::*;
+
+pub mod expire;
+pub use expire::*;
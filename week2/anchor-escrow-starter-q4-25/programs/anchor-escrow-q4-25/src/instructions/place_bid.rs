@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Auction, AuctionBid};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mut,
+        has_one = mint_b,
+        seeds = [b"auction", auction.maker.as_ref(), auction.seed.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        space = AuctionBid::DISCRIMINATOR.len() + AuctionBid::INIT_SPACE,
+        seeds = [b"bid", auction.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Box<Account<'info, AuctionBid>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = mint_b,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub bid_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // The current highest bid, refunded in full when someone else outbids it.
+    // Left empty for the first bid and when the highest bidder raises their own bid.
+    #[account(mut)]
+    pub previous_bidder: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        close = previous_bidder,
+        seeds = [b"bid", auction.key().as_ref(), previous_bid.bidder.as_ref()],
+        bump = previous_bid.bump
+    )]
+    pub previous_bid: Option<Box<Account<'info, AuctionBid>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = previous_bid,
+        associated_token::token_program = token_program
+    )]
+    pub previous_bid_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = bidder,
+        associated_token::mint = mint_b,
+        associated_token::authority = previous_bidder,
+        associated_token::token_program = token_program
+    )]
+    pub previous_bidder_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn place_bid(&mut self, amount: u64, bumps: &PlaceBidBumps) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(now < self.auction.end_time, EscrowError::AuctionEnded);
+
+        let min_bid = self.auction.min_next_bid().ok_or(EscrowError::Overflow)?;
+        require!(amount >= min_bid && amount > 0, EscrowError::BidTooLow);
+
+        match self.auction.highest_bidder {
+            Some(highest) if highest == self.bidder.key() => {
+                // Raising our own bid: only the difference has to be locked.
+                // Our bid passed again as `previous_bid` would be closed on exit
+                require!(self.previous_bid.is_none(), EscrowError::InvalidPreviousBid);
+                let top_up = amount
+                    .checked_sub(self.bid.amount)
+                    .ok_or(EscrowError::Overflow)?;
+                self.deposit(top_up)?;
+            }
+            Some(highest) => {
+                require!(
+                    self.previous_bidder
+                        .as_ref()
+                        .is_some_and(|previous| previous.key() == highest),
+                    EscrowError::InvalidPreviousBid
+                );
+                self.refund_previous_bid()?;
+                self.deposit(amount)?;
+            }
+            None => self.deposit(amount)?,
+        }
+
+        self.bid.set_inner(AuctionBid {
+            auction: self.auction.key(),
+            bidder: self.bidder.key(),
+            amount,
+            bump: bumps.bid,
+        });
+
+        self.auction.highest_bidder = Some(self.bidder.key());
+        self.auction.highest_bid = amount;
+
+        // Anti-sniping: a late bid keeps the auction open for another window
+        let extended_end = now
+            .checked_add(self.auction.extension_window)
+            .ok_or(EscrowError::Overflow)?;
+        if extended_end > self.auction.end_time {
+            self.auction.end_time = extended_end;
+        }
+
+        Ok(())
+    }
+
+    fn deposit(&mut self, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: self.bidder_ata_b.to_account_info(),
+                to: self.bid_vault.to_account_info(),
+                authority: self.bidder.to_account_info(),
+            },
+        );
+
+        transfer_checked(cpi_ctx, amount, self.mint_b.decimals)
+    }
+
+    fn refund_previous_bid(&mut self) -> Result<()> {
+        let (
+            Some(previous_bidder),
+            Some(previous_bid),
+            Some(previous_bid_vault),
+            Some(previous_bidder_ata_b),
+        ) = (
+            self.previous_bidder.as_ref(),
+            self.previous_bid.as_ref(),
+            self.previous_bid_vault.as_ref(),
+            self.previous_bidder_ata_b.as_ref(),
+        )
+        else {
+            return err!(EscrowError::InvalidPreviousBid);
+        };
+        require_keys_eq!(
+            previous_bid.bidder,
+            previous_bidder.key(),
+            EscrowError::InvalidPreviousBid
+        );
+
+        let auction_key = self.auction.key();
+        let previous_bidder_key = previous_bidder.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bid",
+            auction_key.as_ref(),
+            previous_bidder_key.as_ref(),
+            &[previous_bid.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: previous_bid_vault.to_account_info(),
+                to: previous_bidder_ata_b.to_account_info(),
+                authority: previous_bid.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(
+            transfer_ctx,
+            previous_bid_vault.amount,
+            self.mint_b.decimals,
+        )?;
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: previous_bid_vault.to_account_info(),
+                destination: previous_bidder.to_account_info(),
+                authority: previous_bid.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
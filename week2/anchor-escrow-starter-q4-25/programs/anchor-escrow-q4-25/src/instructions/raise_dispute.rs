@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus};
+
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    pub signer: Signer<'info>,
+    #[account(
+        mut,
+        constraint = escrow.status == EscrowStatus::Funded @ EscrowError::InvalidEscrowStatus,
+        seeds = [b"escrow", escrow.maker.as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> RaiseDispute<'info> {
+    pub fn raise_dispute(&mut self) -> Result<()> {
+        let signer = self.signer.key();
+        require!(
+            signer == self.escrow.maker || Some(signer) == self.escrow.taker,
+            EscrowError::NotParticipant
+        );
+
+        self.escrow.status = EscrowStatus::Disputed;
+
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use crate::Escrow;
+use crate::{errors::EscrowError, events::RefundEvent, Escrow, EscrowStatus, OrderBook, OrderSide};
 use anchor_spl::{
     associated_token::AssociatedToken,
     token_interface::{
@@ -24,8 +24,12 @@ pub struct Refund<'info> {
     #[account(
         mut,
         close = maker,
-        has_one = mint_a,
-        has_one = maker,
+        has_one = mint_a @ EscrowError::MintMismatch,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.side == OrderSide::Ask @ EscrowError::WrongSide,
+        constraint = escrow.status == EscrowStatus::Open @ EscrowError::InvalidEscrowStatus,
+        constraint = escrow.milestones.is_empty() @ EscrowError::MilestoneEscrow,
+        constraint = escrow.oracle.is_none() @ EscrowError::OracleEscrow,
         seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
         bump = escrow.bump
     )]
@@ -38,6 +42,13 @@ pub struct Refund<'info> {
         associated_token::token_program = token_program
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    // Required for plain asks, arbitrated escrows are never listed
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Option<Box<Account<'info, OrderBook>>>,
 
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
@@ -45,6 +56,16 @@ pub struct Refund<'info> {
 }
 
 impl<'info> Refund<'info> {
+    pub fn remove_from_book(&mut self) -> Result<()> {
+        match self.order_book.as_mut() {
+            Some(order_book) => {
+                order_book.remove(OrderSide::Ask, &self.escrow.key());
+            }
+            None => require!(!self.escrow.is_listable(), EscrowError::MissingOrderBook),
+        }
+        Ok(())
+    }
+
     pub fn refund_and_close_vault(&mut self) -> Result<()> {
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"escrow",
@@ -68,6 +89,13 @@ impl<'info> Refund<'info> {
 
         transfer_checked(tranfer_cpi_ctx, self.vault.amount, self.mint_a.decimals)?;
 
+        emit!(RefundEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            amount: self.vault.amount,
+        });
+
         let close_accounts = CloseAccount {
             account: self.vault.to_account_info(),
             destination: self.maker.to_account_info(),
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Escrow, MilestoneStatus};
+
+#[derive(Accounts)]
+pub struct RefundMilestones<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = maker,
+        constraint = !escrow.milestones.is_empty() @ EscrowError::NotMilestoneEscrow,
+        seeds = [b"escrow", maker.key().as_ref(), &escrow.seed.to_le_bytes()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundMilestones<'info> {
+    /// Returns every pending tranche to the maker once the deadline has passed
+    /// and closes the escrow. If all tranches are already released it can be
+    /// called early, just to reclaim rent. Tokens sent to the vault outside a
+    /// tranche go back to the maker too, so they can't block the close.
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let pending = self
+            .escrow
+            .milestones
+            .iter()
+            .filter(|milestone| milestone.status == MilestoneStatus::Pending)
+            .try_fold(0u64, |total, milestone| total.checked_add(milestone.amount))
+            .ok_or(EscrowError::Overflow)?;
+        require!(
+            pending == 0 || Clock::get()?.unix_timestamp >= self.escrow.deadline,
+            EscrowError::DeadlineNotReached
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        if self.vault.amount > 0 {
+            let transfer_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let tranfer_cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                transfer_accounts,
+                signer_seeds,
+            );
+
+            transfer_checked(tranfer_cpi_ctx, self.vault.amount, self.mint_a.decimals)?;
+        }
+
+        let close_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let close_cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            signer_seeds,
+        );
+
+        close_account(close_cpi_ctx)
+    }
+}
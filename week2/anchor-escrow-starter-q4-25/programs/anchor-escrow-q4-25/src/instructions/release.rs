@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus};
+
+#[derive(Accounts)]
+pub struct Release<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(mut)]
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = escrow.taker == Some(taker.key()) @ EscrowError::InvalidTaker,
+        constraint = escrow.status == EscrowStatus::Funded @ EscrowError::InvalidEscrowStatus,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Release<'info> {
+    pub fn release_and_close_vaults(&mut self) -> Result<()> {
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_a = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: self.vault.to_account_info(),
+                to: self.taker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(transfer_a, self.vault.amount, self.mint_a.decimals)?;
+
+        let transfer_b = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: self.vault_b.to_account_info(),
+                to: self.maker_ata_b.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        transfer_checked(transfer_b, self.vault_b.amount, self.mint_b.decimals)?;
+
+        let close_a = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_a)?;
+
+        // The taker paid the rent for `vault_b`
+        let close_b = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault_b.to_account_info(),
+                destination: self.taker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+        close_account(close_b)
+    }
+}
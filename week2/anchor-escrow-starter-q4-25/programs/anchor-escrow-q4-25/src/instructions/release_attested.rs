@@ -0,0 +1,163 @@
+use anchor_instruction_sysvar::Ed25519InstructionSignatures;
+use anchor_lang::prelude::*;
+use solana_program::{
+    ed25519_program,
+    sysvar::instructions::{
+        load_current_index_checked, load_instruction_at_checked, ID as InstructionSysvarId,
+    },
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Escrow};
+
+#[derive(Accounts)]
+pub struct ReleaseAttested<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    // Gets the rent back
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = escrow.oracle.is_some() @ EscrowError::NotOracleEscrow,
+        constraint = escrow.taker == Some(taker.key()) @ EscrowError::InvalidTaker,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: instruction sysvar
+    #[account(address = InstructionSysvarId)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ReleaseAttested<'info> {
+    /// Checks that the instruction right before this one is an ed25519
+    /// verification of `Escrow::attestation_message` by the escrow's oracle.
+    /// The ed25519 program has already rejected the transaction if the
+    /// signature itself is bad, so only the signer and message are checked here.
+    pub fn verify_attestation(&self) -> Result<()> {
+        let current_index = load_current_index_checked(&self.instructions)?;
+        require!(current_index > 0, EscrowError::MissingAttestation);
+
+        let ed25519_ix =
+            load_instruction_at_checked((current_index - 1) as usize, &self.instructions)?;
+        require_keys_eq!(
+            ed25519_ix.program_id,
+            ed25519_program::ID,
+            EscrowError::MissingAttestation
+        );
+
+        require!(
+            ed25519_ix.accounts.is_empty(),
+            EscrowError::InvalidAttestation
+        );
+
+        let signatures = Ed25519InstructionSignatures::unpack(&ed25519_ix.data)
+            .map_err(|_| EscrowError::InvalidAttestation)?
+            .0;
+        require_eq!(signatures.len(), 1, EscrowError::InvalidAttestation);
+
+        // Only signatures whose pubkey, signature and message all live in the
+        // ed25519 instruction itself are verifiable, otherwise the verified
+        // bytes could differ from the ones read below
+        let signature = &signatures[0];
+        require!(signature.is_verifiable, EscrowError::InvalidAttestation);
+
+        let signer = signature
+            .public_key
+            .ok_or(EscrowError::InvalidAttestation)?;
+        require!(
+            Some(signer) == self.escrow.oracle,
+            EscrowError::InvalidAttestation
+        );
+
+        let message = signature
+            .message
+            .as_ref()
+            .ok_or(EscrowError::InvalidAttestation)?;
+        require!(
+            message.as_slice()
+                == self
+                    .escrow
+                    .attestation_message(&self.escrow.key())
+                    .as_slice(),
+            EscrowError::InvalidAttestation
+        );
+
+        Ok(())
+    }
+
+    pub fn release_and_close_vault(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= self.escrow.deadline,
+            EscrowError::DeadlinePassed
+        );
+
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: self.vault.to_account_info(),
+                to: self.taker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
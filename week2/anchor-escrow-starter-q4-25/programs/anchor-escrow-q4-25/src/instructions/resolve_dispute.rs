@@ -0,0 +1,209 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Escrow, EscrowStatus, BPS_DENOMINATOR};
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(mut)]
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = escrow.arbiter == Some(arbiter.key()) @ EscrowError::NotArbiter,
+        constraint = escrow.taker == Some(taker.key()) @ EscrowError::InvalidTaker,
+        constraint = escrow.status == EscrowStatus::Disputed @ EscrowError::InvalidEscrowStatus,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint_a,
+        associated_token::authority = arbiter,
+        associated_token::token_program = token_program
+    )]
+    pub arbiter_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = arbiter,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveDispute<'info> {
+    /// `maker_share_bps` is how much of the trade goes through: 10_000 releases
+    /// everything to the maker as if the escrow had been released, 0 refunds the
+    /// taker in full, anything in between settles that fraction of both legs.
+    /// The arbiter's fee is `arbiter_fee_bps` of the deposit, paid by each
+    /// side in proportion to how much of the ruling went against it.
+    pub fn resolve_dispute(&mut self, maker_share_bps: u16) -> Result<()> {
+        let maker_share_bps = maker_share_bps as u64;
+        require!(maker_share_bps <= BPS_DENOMINATOR, EscrowError::InvalidBps);
+
+        let maker_b = bps_of(self.vault_b.amount, maker_share_bps)?;
+        let taker_b = self.vault_b.amount - maker_b;
+
+        let fee = bps_of(self.vault.amount, self.escrow.arbiter_fee_bps as u64)?;
+        // The maker's part rounds up, their share of the deposit always covers it
+        let taker_fee = bps_of(fee, maker_share_bps)?;
+        let taker_a = bps_of(self.vault.amount, maker_share_bps)?
+            .checked_sub(taker_fee)
+            .ok_or(EscrowError::Overflow)?;
+        let maker_a = self
+            .vault
+            .amount
+            .checked_sub(fee)
+            .and_then(|rest| rest.checked_sub(taker_a))
+            .ok_or(EscrowError::Overflow)?;
+
+        let vault_b = self.vault_b.to_account_info();
+        let vault = self.vault.to_account_info();
+        self.pay_out(&vault_b, &self.mint_b, &self.maker_ata_b, maker_b)?;
+        self.pay_out(&vault_b, &self.mint_b, &self.taker_ata_b, taker_b)?;
+        self.pay_out(&vault, &self.mint_a, &self.arbiter_ata_a, fee)?;
+        self.pay_out(&vault, &self.mint_a, &self.taker_ata_a, taker_a)?;
+        self.pay_out(&vault, &self.mint_a, &self.maker_ata_a, maker_a)?;
+
+        self.close_vault(&vault, self.maker.to_account_info())?;
+        self.close_vault(&vault_b, self.taker.to_account_info())
+    }
+
+    fn pay_out(
+        &self,
+        from: &AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: mint.to_account_info(),
+                from: from.clone(),
+                to: to.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)
+    }
+
+    fn close_vault(
+        &self,
+        vault: &AccountInfo<'info>,
+        destination: AccountInfo<'info>,
+    ) -> Result<()> {
+        let maker_key = self.maker.key();
+        let seed = self.escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.escrow.bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.clone(),
+                destination,
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(cpi_ctx)
+    }
+}
+
+fn bps_of(amount: u64, bps: u64) -> Result<u64> {
+    let share = (amount as u128)
+        .checked_mul(bps as u128)
+        .ok_or(EscrowError::Overflow)?
+        / BPS_DENOMINATOR as u128;
+
+    Ok(share as u64)
+}
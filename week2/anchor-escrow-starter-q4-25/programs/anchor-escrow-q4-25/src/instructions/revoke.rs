@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Vesting};
+
+#[derive(Accounts)]
+pub struct Revoke<'info> {
+    #[account(mut)]
+    pub grantor: Signer<'info>,
+    pub beneficiary: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = grantor,
+        associated_token::token_program = token_program
+    )]
+    pub grantor_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = grantor,
+        has_one = grantor,
+        has_one = beneficiary,
+        has_one = mint,
+        constraint = vesting.revocable @ EscrowError::NotRevocable,
+        seeds = [b"vesting", grantor.key().as_ref(), vesting.seed.to_le_bytes().as_ref()],
+        bump = vesting.bump
+    )]
+    pub vesting: Account<'info, Vesting>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = grantor,
+        associated_token::mint = mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program
+    )]
+    pub beneficiary_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Revoke<'info> {
+    /// Pays out whatever has vested but not been claimed yet, then returns the
+    /// unvested remainder to the grantor and closes the grant.
+    pub fn revoke_and_close_vault(&mut self) -> Result<()> {
+        let claimable = self.vesting.claimable(Clock::get()?.unix_timestamp)?;
+        let unvested = self
+            .vault
+            .amount
+            .checked_sub(claimable)
+            .ok_or(EscrowError::Overflow)?;
+
+        let grantor_key = self.grantor.key();
+        let seed = self.vesting.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vesting",
+            grantor_key.as_ref(),
+            seed.as_ref(),
+            &[self.vesting.bump],
+        ]];
+
+        if claimable > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    mint: self.mint.to_account_info(),
+                    from: self.vault.to_account_info(),
+                    to: self.beneficiary_ata.to_account_info(),
+                    authority: self.vesting.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer_checked(transfer_ctx, claimable, self.mint.decimals)?;
+        }
+
+        if unvested > 0 {
+            let transfer_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    mint: self.mint.to_account_info(),
+                    from: self.vault.to_account_info(),
+                    to: self.grantor_ata.to_account_info(),
+                    authority: self.vesting.to_account_info(),
+                },
+                signer_seeds,
+            );
+            transfer_checked(transfer_ctx, unvested, self.mint.decimals)?;
+        }
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.grantor.to_account_info(),
+                authority: self.vesting.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,213 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, Auction, AuctionBid};
+
+#[derive(Accounts)]
+pub struct Settle<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"auction", maker.key().as_ref(), auction.seed.to_le_bytes().as_ref()],
+        bump = auction.bump
+    )]
+    pub auction: Box<Account<'info, Auction>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = auction,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    // Only needed when the auction ended without bids
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    // Only needed when the auction has a winner
+    #[account(mut)]
+    pub winner: Option<SystemAccount<'info>>,
+    #[account(
+        mut,
+        close = winner,
+        seeds = [b"bid", auction.key().as_ref(), winning_bid.bidder.as_ref()],
+        bump = winning_bid.bump
+    )]
+    pub winning_bid: Option<Box<Account<'info, AuctionBid>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = winning_bid,
+        associated_token::token_program = token_program
+    )]
+    pub bid_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = mint_a,
+        associated_token::authority = winner,
+        associated_token::token_program = token_program
+    )]
+    pub winner_ata_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        init_if_needed,
+        payer = settler,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Settle<'info> {
+    pub fn settle(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.auction.end_time,
+            EscrowError::AuctionNotEnded
+        );
+
+        match self.auction.highest_bidder {
+            Some(highest) => {
+                require!(
+                    self.winner
+                        .as_ref()
+                        .is_some_and(|winner| winner.key() == highest),
+                    EscrowError::InvalidWinningBid
+                );
+                self.pay_maker()?;
+                let winner_ata_a = self
+                    .winner_ata_a
+                    .as_ref()
+                    .ok_or(EscrowError::InvalidWinningBid)?
+                    .to_account_info();
+                self.withdraw_and_close_vault(winner_ata_a)
+            }
+            None => {
+                let maker_ata_a = self
+                    .maker_ata_a
+                    .as_ref()
+                    .ok_or(EscrowError::MissingTokenAccount)?
+                    .to_account_info();
+                self.withdraw_and_close_vault(maker_ata_a)
+            }
+        }
+    }
+
+    fn pay_maker(&mut self) -> Result<()> {
+        let (Some(winner), Some(winning_bid), Some(bid_vault), Some(maker_ata_b)) = (
+            self.winner.as_ref(),
+            self.winning_bid.as_ref(),
+            self.bid_vault.as_ref(),
+            self.maker_ata_b.as_ref(),
+        ) else {
+            return err!(EscrowError::InvalidWinningBid);
+        };
+        require_keys_eq!(
+            winning_bid.bidder,
+            winner.key(),
+            EscrowError::InvalidWinningBid
+        );
+
+        let auction_key = self.auction.key();
+        let winner_key = winner.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"bid",
+            auction_key.as_ref(),
+            winner_key.as_ref(),
+            &[winning_bid.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: bid_vault.to_account_info(),
+                to: maker_ata_b.to_account_info(),
+                authority: winning_bid.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, bid_vault.amount, self.mint_b.decimals)?;
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: bid_vault.to_account_info(),
+                destination: winner.to_account_info(),
+                authority: winning_bid.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+
+    fn withdraw_and_close_vault(&mut self, to: AccountInfo<'info>) -> Result<()> {
+        let maker_key = self.maker.key();
+        let seed = self.auction.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"auction",
+            maker_key.as_ref(),
+            seed.as_ref(),
+            &[self.auction.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: self.vault.to_account_info(),
+                to,
+                authority: self.auction.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: self.vault.to_account_info(),
+                destination: self.maker.to_account_info(),
+                authority: self.auction.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
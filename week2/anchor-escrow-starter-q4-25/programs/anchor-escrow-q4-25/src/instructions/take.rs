@@ -10,7 +10,7 @@ use anchor_spl::{
     },
 };
 
-use crate::Escrow;
+use crate::{errors::EscrowError, events::TakeEvent, Escrow, OrderBook, OrderSide};
 
 #[derive(Accounts)]
 pub struct Take<'info> {
@@ -20,12 +20,12 @@ pub struct Take<'info> {
     pub maker: SystemAccount<'info>,
     #[account(
         mint::token_program = token_program,
-        constraint = mint_a.key() == escrow.mint_a
+        constraint = mint_a.key() == escrow.mint_a @ EscrowError::MintMismatch
     )]
     pub mint_a: Box<InterfaceAccount<'info, Mint>>,
     #[account(
         mint::token_program = token_program,
-        constraint = mint_b.key() == escrow.mint_b
+        constraint = mint_b.key() == escrow.mint_b @ EscrowError::MintMismatch
     )]
     pub mint_b: Box<InterfaceAccount<'info, Mint>>,
     #[account(
@@ -54,6 +54,11 @@ pub struct Take<'info> {
     #[account(
         mut,
         close = taker,
+        has_one = maker @ EscrowError::InvalidMaker,
+        constraint = escrow.side == OrderSide::Ask @ EscrowError::WrongSide,
+        constraint = escrow.arbiter.is_none() @ EscrowError::ArbitratedEscrow,
+        constraint = escrow.milestones.is_empty() @ EscrowError::MilestoneEscrow,
+        constraint = escrow.oracle.is_none() @ EscrowError::OracleEscrow,
         seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
         bump = escrow.bump
     )]
@@ -65,6 +70,12 @@ pub struct Take<'info> {
         associated_token::token_program = token_program
     )]
     pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
@@ -73,8 +84,18 @@ pub struct Take<'info> {
 impl<'info> Take<'info> {
     //  TODO: Implement Take Instruction
     //  Includes Deposit, Withdraw and Close Vault
+    pub fn check_terms(&self, expected_receive: u64, min_amount_a: u64) -> Result<()> {
+        self.escrow
+            .check_terms(self.vault.amount, expected_receive, min_amount_a)
+    }
+
     pub fn deposit(&mut self) -> Result<()> {
         let amount = self.escrow.receive;
+        require!(
+            self.taker_ata_b.amount >= amount,
+            EscrowError::InsufficientTakerBalance
+        );
+
         let cpi_ctx = CpiContext::new(
             self.token_program.to_account_info(),
             TransferChecked {
@@ -110,7 +131,18 @@ impl<'info> Take<'info> {
             signer_seeds,
         );
 
-        transfer_checked(cpi_ctx, amount, self.mint_a.decimals)
+        transfer_checked(cpi_ctx, amount, self.mint_a.decimals)?;
+
+        emit!(TakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount_a: amount,
+            amount_b: self.escrow.receive,
+        });
+        Ok(())
     }
 
     pub fn close_vault(&mut self) -> Result<()> {
@@ -135,4 +167,9 @@ impl<'info> Take<'info> {
 
         close_account(cpi_ctx)
     }
+
+    pub fn remove_from_book(&mut self) -> Result<()> {
+        self.order_book.remove(OrderSide::Ask, &self.escrow.key());
+        Ok(())
+    }
 }
@@ -0,0 +1,244 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::{
+        create_idempotent, get_associated_token_address_with_program_id, AssociatedToken, Create,
+    },
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{errors::EscrowError, events::TakeEvent, Escrow, OrderBook, OrderSide};
+
+// Each escrow is passed in `remaining_accounts` as [escrow, vault, maker, maker_ata_b]
+pub const TAKE_MANY_GROUP_LEN: usize = 4;
+
+// The terms the taker quoted for one escrow, same as `take`'s arguments
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct TakeLimits {
+    pub expected_receive: u64,
+    pub min_amount_a: u64,
+}
+
+#[derive(Accounts)]
+pub struct TakeMany<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"book", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Box<Account<'info, OrderBook>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeMany<'info> {
+    pub fn take_many(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        max_total_receive: u64,
+        limits: &[TakeLimits],
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty()
+                && remaining_accounts.len() % TAKE_MANY_GROUP_LEN == 0
+                && remaining_accounts.len() / TAKE_MANY_GROUP_LEN == limits.len(),
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        let mut total_receive = 0u64;
+        for (group, limits) in remaining_accounts.chunks(TAKE_MANY_GROUP_LEN).zip(limits) {
+            let escrow = self.validate_escrow(&group[0], &group[2])?;
+
+            total_receive = total_receive
+                .checked_add(escrow.receive)
+                .ok_or(EscrowError::Overflow)?;
+            require!(
+                total_receive <= max_total_receive,
+                EscrowError::TotalReceiveExceeded
+            );
+            // `taker_ata_b` isn't reloaded, so this covers every escrow so far
+            require!(
+                self.taker_ata_b.amount >= total_receive,
+                EscrowError::InsufficientTakerBalance
+            );
+
+            self.order_book.remove(OrderSide::Ask, &escrow.key());
+            self.deposit(&escrow, &group[2], &group[3])?;
+            self.withdraw_and_close_vault(&escrow, &group[1], limits)?;
+            escrow.close(self.taker.to_account_info())?;
+        }
+
+        Ok(())
+    }
+
+    // Same checks `Take` gets from its account constraints
+    fn validate_escrow(
+        &self,
+        escrow_info: &'info AccountInfo<'info>,
+        maker: &AccountInfo<'info>,
+    ) -> Result<Account<'info, Escrow>> {
+        require!(
+            escrow_info.is_writable,
+            EscrowError::InvalidRemainingAccounts
+        );
+        let escrow = Account::<Escrow>::try_from(escrow_info)?;
+
+        require_keys_eq!(escrow.maker, maker.key(), EscrowError::InvalidMaker);
+        require_keys_eq!(escrow.mint_a, self.mint_a.key(), EscrowError::MintMismatch);
+        require_keys_eq!(escrow.mint_b, self.mint_b.key(), EscrowError::MintMismatch);
+        require!(escrow.side == OrderSide::Ask, EscrowError::WrongSide);
+        require!(escrow.arbiter.is_none(), EscrowError::ArbitratedEscrow);
+        require!(escrow.milestones.is_empty(), EscrowError::MilestoneEscrow);
+        require!(escrow.oracle.is_none(), EscrowError::OracleEscrow);
+
+        let expected_escrow = Pubkey::create_program_address(
+            &[
+                b"escrow",
+                maker.key().as_ref(),
+                escrow.seed.to_le_bytes().as_ref(),
+                &[escrow.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(EscrowError::InvalidRemainingAccounts))?;
+        require_keys_eq!(
+            expected_escrow,
+            escrow.key(),
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        Ok(escrow)
+    }
+
+    fn deposit(
+        &self,
+        escrow: &Account<'info, Escrow>,
+        maker: &AccountInfo<'info>,
+        maker_ata_b: &AccountInfo<'info>,
+    ) -> Result<()> {
+        require_keys_eq!(
+            maker_ata_b.key(),
+            get_associated_token_address_with_program_id(
+                &maker.key(),
+                &self.mint_b.key(),
+                &self.token_program.key()
+            ),
+            EscrowError::InvalidRemainingAccounts
+        );
+
+        create_idempotent(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            Create {
+                payer: self.taker.to_account_info(),
+                associated_token: maker_ata_b.clone(),
+                authority: maker.clone(),
+                mint: self.mint_b.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_b.to_account_info(),
+                from: self.taker_ata_b.to_account_info(),
+                to: maker_ata_b.clone(),
+                authority: self.taker.to_account_info(),
+            },
+        );
+
+        transfer_checked(cpi_ctx, escrow.receive, self.mint_b.decimals)
+    }
+
+    fn withdraw_and_close_vault(
+        &self,
+        escrow: &Account<'info, Escrow>,
+        vault_info: &'info AccountInfo<'info>,
+        limits: &TakeLimits,
+    ) -> Result<()> {
+        require_keys_eq!(
+            vault_info.key(),
+            get_associated_token_address_with_program_id(
+                &escrow.key(),
+                &self.mint_a.key(),
+                &self.token_program.key()
+            ),
+            EscrowError::InvalidRemainingAccounts
+        );
+        let vault = InterfaceAccount::<TokenAccount>::try_from(vault_info)?;
+        escrow.check_terms(vault.amount, limits.expected_receive, limits.min_amount_a)?;
+
+        let seed = escrow.seed.to_le_bytes();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            escrow.maker.as_ref(),
+            seed.as_ref(),
+            &[escrow.bump],
+        ]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                mint: self.mint_a.to_account_info(),
+                from: vault.to_account_info(),
+                to: self.taker_ata_a.to_account_info(),
+                authority: escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(transfer_ctx, vault.amount, self.mint_a.decimals)?;
+
+        emit!(TakeEvent {
+            escrow: escrow.key(),
+            maker: escrow.maker,
+            taker: self.taker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount_a: vault.amount,
+            amount_b: escrow.receive,
+        });
+
+        let close_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            CloseAccount {
+                account: vault.to_account_info(),
+                destination: self.taker.to_account_info(),
+                authority: escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        close_account(close_ctx)
+    }
+}
@@ -0,0 +1,135 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::EscrowError, events::EscrowUpdated, BookEntry, Escrow, EscrowStatus, OrderBook,
+};
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct UpdateEscrow<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    // mint_a for asks, mint_b for bids
+    #[account(
+        mint::token_program = token_program,
+        constraint = deposit_mint.key() == escrow.vault_mint() @ EscrowError::MintMismatch
+    )]
+    pub deposit_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = deposit_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = maker,
+        constraint = escrow.status == EscrowStatus::Open @ EscrowError::InvalidEscrowStatus,
+        constraint = escrow.milestones.is_empty() @ EscrowError::MilestoneEscrow,
+        constraint = escrow.oracle.is_none() @ EscrowError::OracleEscrow,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = deposit_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    // Required for plain asks and bids, arbitrated escrows are never listed
+    #[account(
+        mut,
+        seeds = [b"book", escrow.mint_a.as_ref(), escrow.mint_b.as_ref()],
+        bump = order_book.bump
+    )]
+    pub order_book: Option<Box<Account<'info, OrderBook>>>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UpdateEscrow<'info> {
+    pub fn update_escrow(&mut self, receive: u64, top_up: u64, withdraw: u64) -> Result<()> {
+        require!(receive > 0, EscrowError::InvalidAmount);
+        require!(top_up == 0 || withdraw == 0, EscrowError::ConflictingUpdate);
+
+        let old_receive = self.escrow.receive;
+        let old_deposit = self.vault.amount;
+
+        if top_up > 0 {
+            self.top_up(top_up)?;
+        }
+        if withdraw > 0 {
+            require!(
+                withdraw < self.vault.amount,
+                EscrowError::WithdrawExceedsVault
+            );
+            self.withdraw(withdraw)?;
+        }
+
+        self.vault.reload()?;
+        self.escrow.receive = receive;
+
+        match self.order_book.as_mut() {
+            Some(order_book) => {
+                let entry = BookEntry::new(self.escrow.key(), &self.escrow, self.vault.amount);
+                order_book.reprice(self.escrow.side, entry)?;
+            }
+            None => require!(!self.escrow.is_listable(), EscrowError::MissingOrderBook),
+        }
+
+        emit!(EscrowUpdated {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            old_receive,
+            new_receive: receive,
+            old_deposit,
+            new_deposit: self.vault.amount,
+        });
+
+        Ok(())
+    }
+
+    fn top_up(&mut self, amount: u64) -> Result<()> {
+        let cpi_ctx = CpiContext::new(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.maker_ata.to_account_info(),
+                mint: self.deposit_mint.to_account_info(),
+                to: self.vault.to_account_info(),
+                authority: self.maker.to_account_info(),
+            },
+        );
+
+        transfer_checked(cpi_ctx, amount, self.deposit_mint.decimals)
+    }
+
+    fn withdraw(&mut self, amount: u64) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed.to_le_bytes(),
+            &[self.escrow.bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.deposit_mint.to_account_info(),
+                to: self.maker_ata.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, amount, self.deposit_mint.decimals)
+    }
+}
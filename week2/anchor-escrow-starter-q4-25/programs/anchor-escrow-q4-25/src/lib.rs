@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+pub mod errors;
+pub mod events;
 pub mod instructions;
 pub mod state;
 
@@ -13,16 +15,164 @@ pub mod anchor_escrow_q4_25 {
     use super::*;
     pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
         ctx.accounts.deposit(deposit)?;
-        ctx.accounts.init_escrow(seed, receive, &ctx.bumps)
+        ctx.accounts
+            .init_escrow(seed, deposit, receive, &ctx.bumps)?;
+        ctx.accounts.add_to_book(deposit, &ctx.bumps)
     }
 
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
+        ctx.accounts.remove_from_book()?;
         ctx.accounts.refund_and_close_vault()
     }
 
-    pub fn take(ctx: Context<Take>) -> Result<()> {
+    pub fn take(ctx: Context<Take>, expected_receive: u64, min_amount_a: u64) -> Result<()> {
+        ctx.accounts.check_terms(expected_receive, min_amount_a)?;
+        ctx.accounts.remove_from_book()?;
         ctx.accounts.deposit()?;
         ctx.accounts.withdraw()?;
         ctx.accounts.close_vault()
     }
+
+    pub fn make_auction(
+        ctx: Context<MakeAuction>,
+        seed: u64,
+        deposit: u64,
+        reserve_price: u64,
+        min_increment: u64,
+        end_time: i64,
+        extension_window: i64,
+    ) -> Result<()> {
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts.init_auction(
+            seed,
+            reserve_price,
+            min_increment,
+            end_time,
+            extension_window,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64) -> Result<()> {
+        ctx.accounts.place_bid(amount, &ctx.bumps)
+    }
+
+    pub fn settle(ctx: Context<Settle>) -> Result<()> {
+        ctx.accounts.settle()
+    }
+
+    pub fn make_arbitrated(
+        ctx: Context<MakeArbitrated>,
+        seed: u64,
+        deposit: u64,
+        receive: u64,
+        arbiter_fee_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts
+            .init_escrow(seed, receive, arbiter_fee_bps, &ctx.bumps)
+    }
+
+    pub fn fund_escrow(ctx: Context<FundEscrow>) -> Result<()> {
+        ctx.accounts.fund()
+    }
+
+    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        ctx.accounts.raise_dispute()
+    }
+
+    pub fn release(ctx: Context<Release>) -> Result<()> {
+        ctx.accounts.release_and_close_vaults()
+    }
+
+    pub fn resolve_dispute(ctx: Context<ResolveDispute>, maker_share_bps: u16) -> Result<()> {
+        ctx.accounts.resolve_dispute(maker_share_bps)
+    }
+
+    pub fn make_milestone(
+        ctx: Context<MakeMilestone>,
+        seed: u64,
+        amounts: Vec<u64>,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.deposit(&amounts)?;
+        ctx.accounts
+            .init_escrow(seed, amounts, deadline, &ctx.bumps)
+    }
+
+    pub fn approve_milestone(ctx: Context<ApproveMilestone>, index: u8) -> Result<()> {
+        ctx.accounts.approve_milestone(index)
+    }
+
+    pub fn refund_milestones(ctx: Context<RefundMilestones>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    pub fn take_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, TakeMany<'info>>,
+        max_total_receive: u64,
+        limits: Vec<TakeLimits>,
+    ) -> Result<()> {
+        ctx.accounts
+            .take_many(ctx.remaining_accounts, max_total_receive, &limits)
+    }
+
+    pub fn make_bid(ctx: Context<MakeBid>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts.init_escrow(seed, receive, &ctx.bumps)?;
+        ctx.accounts.add_to_book(deposit, &ctx.bumps)
+    }
+
+    pub fn fill_bid(ctx: Context<FillBid>, expected_receive: u64, min_amount_b: u64) -> Result<()> {
+        ctx.accounts.check_terms(expected_receive, min_amount_b)?;
+        ctx.accounts.remove_from_book()?;
+        ctx.accounts.deposit()?;
+        ctx.accounts.withdraw_and_close_vault()
+    }
+
+    pub fn cancel_bid(ctx: Context<CancelBid>) -> Result<()> {
+        ctx.accounts.remove_from_book()?;
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    pub fn update_escrow(
+        ctx: Context<UpdateEscrow>,
+        receive: u64,
+        top_up: u64,
+        withdraw: u64,
+    ) -> Result<()> {
+        ctx.accounts.update_escrow(receive, top_up, withdraw)
+    }
+
+    pub fn make_vesting(ctx: Context<MakeVesting>, seed: u64, args: MakeVestingArgs) -> Result<()> {
+        ctx.accounts.deposit(args.amount)?;
+        ctx.accounts.init_vesting(seed, &args, &ctx.bumps)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.claim_vested()
+    }
+
+    pub fn revoke(ctx: Context<Revoke>) -> Result<()> {
+        ctx.accounts.revoke_and_close_vault()
+    }
+
+    pub fn make_oracle(
+        ctx: Context<MakeOracle>,
+        seed: u64,
+        deposit: u64,
+        deadline: i64,
+    ) -> Result<()> {
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts.init_escrow(seed, deadline, &ctx.bumps)
+    }
+
+    pub fn release_attested(ctx: Context<ReleaseAttested>) -> Result<()> {
+        ctx.accounts.verify_attestation()?;
+        ctx.accounts.release_and_close_vault()
+    }
+
+    pub fn expire(ctx: Context<Expire>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()
+    }
 }
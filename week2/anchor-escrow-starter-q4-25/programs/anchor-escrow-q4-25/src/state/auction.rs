@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Auction {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub reserve_price: u64,
+    pub min_increment: u64,
+    pub end_time: i64,
+    // Bids landing this close to the end push `end_time` out to now + window
+    pub extension_window: i64,
+    pub highest_bidder: Option<Pubkey>,
+    pub highest_bid: u64,
+    pub bump: u8,
+}
+
+impl Auction {
+    pub fn min_next_bid(&self) -> Option<u64> {
+        match self.highest_bidder {
+            Some(_) => self.highest_bid.checked_add(self.min_increment),
+            None => Some(self.reserve_price),
+        }
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AuctionBid {
+    pub auction: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
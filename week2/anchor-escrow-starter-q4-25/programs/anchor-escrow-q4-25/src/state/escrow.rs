@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EscrowError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    // Ask: the vault holds mint_a and `receive` is in mint_b.
+    // Bid: the vault holds mint_b and `receive` is in mint_a.
+    pub side: OrderSide,
+    pub arbiter: Option<Pubkey>,
+    pub arbiter_fee_bps: u16,
+    // Counterparty: the taker of an arbitrated or oracle escrow, or the payee of a milestone escrow
+    pub taker: Option<Pubkey>,
+    // Oracle escrow: releases to `taker` on an attestation signed by this key
+    pub oracle: Option<Pubkey>,
+    pub status: EscrowStatus,
+    #[max_len(MAX_MILESTONES)]
+    pub milestones: Vec<Milestone>,
+    // Milestone and oracle escrows: the maker can reclaim what is left after this timestamp
+    pub deadline: i64,
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub fn vault_mint(&self) -> Pubkey {
+        match self.side {
+            OrderSide::Ask => self.mint_a,
+            OrderSide::Bid => self.mint_b,
+        }
+    }
+
+    /// Plain asks and bids, the only escrows `make` and `make_bid` list.
+    /// Anything that can change or close them has to update the book too.
+    pub fn is_listable(&self) -> bool {
+        self.arbiter.is_none() && self.milestones.is_empty() && self.oracle.is_none()
+    }
+
+    /// Guards takers against `update_escrow` landing first: the price may only
+    /// have dropped since they quoted `expected_receive`, and the vault must
+    /// still hold at least `min_deposit`.
+    pub fn check_terms(&self, deposit: u64, expected_receive: u64, min_deposit: u64) -> Result<()> {
+        require!(
+            self.receive <= expected_receive && deposit >= min_deposit,
+            EscrowError::TermsChanged
+        );
+        Ok(())
+    }
+
+    /// The message an oracle signs to release this escrow. Binding the taker
+    /// and deadline keeps an attestation from being replayed against a later
+    /// escrow opened at the same address.
+    pub fn attestation_message(&self, key: &Pubkey) -> Vec<u8> {
+        let mut message = Vec::with_capacity(72);
+        message.extend_from_slice(key.as_ref());
+        message.extend_from_slice(self.taker.unwrap_or_default().as_ref());
+        message.extend_from_slice(&self.deadline.to_le_bytes());
+        message
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OrderSide {
+    Ask,
+    Bid,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum EscrowStatus {
+    // Waiting for a taker
+    Open,
+    // Arbitrated escrow: the taker has deposited mint_b into `vault_b`
+    Funded,
+    // Arbitrated escrow: waiting for the arbiter's ruling
+    Disputed,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub status: MilestoneStatus,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneStatus {
+    Pending,
+    Released,
+}
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// An arbiter fee above 10% would make ruling for oneself worth more than the trade
+pub const MAX_ARBITER_FEE_BPS: u16 = 1_000;
+pub const MAX_MILESTONES: usize = 10;
@@ -1,12 +1,11 @@
-use anchor_lang::prelude::*;
+pub mod escrow;
+pub use escrow::*;
 
-#[account]
-#[derive(InitSpace)]
-pub struct Escrow {
-    pub seed: u64,
-    pub maker: Pubkey,
-    pub mint_a: Pubkey,
-    pub mint_b: Pubkey,
-    pub receive: u64,
-    pub bump: u8
-}
\ No newline at end of file
+pub mod auction;
+pub use auction::*;
+
+pub mod vesting;
+pub use vesting::*;
+
+pub mod order_book;
+pub use order_book::*;
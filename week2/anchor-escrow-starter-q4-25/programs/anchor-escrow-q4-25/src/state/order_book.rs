@@ -0,0 +1,100 @@
+use std::cmp::Ordering;
+
+use anchor_lang::prelude::*;
+
+use crate::{errors::EscrowError, Escrow, OrderSide};
+
+pub const MAX_BOOK_ORDERS: usize = 32;
+
+// One per mint pair. Asks are kept cheapest first and bids highest first, so
+// index 0 of either side is always the best quote.
+#[account]
+#[derive(InitSpace)]
+pub struct OrderBook {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    #[max_len(MAX_BOOK_ORDERS)]
+    pub asks: Vec<BookEntry>,
+    #[max_len(MAX_BOOK_ORDERS)]
+    pub bids: Vec<BookEntry>,
+    pub bump: u8,
+}
+
+// Price is amount_b / amount_a, i.e. mint_b paid per mint_a, on both sides.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct BookEntry {
+    pub escrow: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+impl BookEntry {
+    pub fn new(key: Pubkey, escrow: &Escrow, deposit: u64) -> Self {
+        let (amount_a, amount_b) = match escrow.side {
+            OrderSide::Ask => (deposit, escrow.receive),
+            OrderSide::Bid => (escrow.receive, deposit),
+        };
+        Self {
+            escrow: key,
+            amount_a,
+            amount_b,
+        }
+    }
+
+    fn cmp_price(&self, other: &Self) -> Ordering {
+        (self.amount_b as u128 * other.amount_a as u128)
+            .cmp(&(other.amount_b as u128 * self.amount_a as u128))
+    }
+
+    fn is_better_than(&self, other: &Self, side: OrderSide) -> bool {
+        match side {
+            OrderSide::Ask => self.cmp_price(other) == Ordering::Less,
+            OrderSide::Bid => self.cmp_price(other) == Ordering::Greater,
+        }
+    }
+}
+
+impl OrderBook {
+    /// Inserts behind every order at the same or a better price. When the side
+    /// is full the worst order drops off; an order worse than all of them is
+    /// simply not listed, the escrow itself is unaffected. Zero amounts have no
+    /// price and are rejected.
+    pub fn insert(&mut self, side: OrderSide, entry: BookEntry) -> Result<()> {
+        require!(
+            entry.amount_a > 0 && entry.amount_b > 0,
+            EscrowError::InvalidAmount
+        );
+
+        let orders = self.orders_mut(side);
+        let position = orders
+            .iter()
+            .position(|order| entry.is_better_than(order, side))
+            .unwrap_or(orders.len());
+        if position < MAX_BOOK_ORDERS {
+            orders.insert(position, entry);
+            orders.truncate(MAX_BOOK_ORDERS);
+        }
+        Ok(())
+    }
+
+    pub fn remove(&mut self, side: OrderSide, escrow: &Pubkey) -> Option<BookEntry> {
+        let orders = self.orders_mut(side);
+        let index = orders.iter().position(|order| order.escrow == *escrow)?;
+        Some(orders.remove(index))
+    }
+
+    /// Moves a listed order to its new price. Unlisted orders stay unlisted.
+    pub fn reprice(&mut self, side: OrderSide, entry: BookEntry) -> Result<()> {
+        if self.remove(side, &entry.escrow).is_some() {
+            self.insert(side, entry)?;
+        }
+        Ok(())
+    }
+
+    fn orders_mut(&mut self, side: OrderSide) -> &mut Vec<BookEntry> {
+        match side {
+            OrderSide::Ask => &mut self.asks,
+            OrderSide::Bid => &mut self.bids,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::EscrowError;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    pub seed: u64,
+    pub grantor: Pubkey,
+    pub beneficiary: Pubkey,
+    pub mint: Pubkey,
+    pub total: u64,
+    pub claimed: u64,
+    pub start: i64,
+    // Seconds after `start` before anything unlocks
+    pub cliff: i64,
+    // Seconds after `start` until the whole grant is unlocked
+    pub duration: i64,
+    pub revocable: bool,
+    pub bump: u8,
+}
+
+impl Vesting {
+    /// Unlocks linearly from `start`, with nothing claimable before the cliff.
+    pub fn vested_amount(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.start);
+        if elapsed < self.cliff {
+            return Ok(0);
+        }
+        if elapsed >= self.duration {
+            return Ok(self.total);
+        }
+
+        let vested = (self.total as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(EscrowError::Overflow)?
+            / self.duration as u128;
+
+        Ok(vested as u64)
+    }
+
+    pub fn claimable(&self, now: i64) -> Result<u64> {
+        self.vested_amount(now)?
+            .checked_sub(self.claimed)
+            .ok_or(error!(EscrowError::Overflow))
+    }
+}
@@ -24,10 +24,39 @@ describe("anchor_escrow_q4_25", () => {
   let escrowPda: anchor.web3.PublicKey;
   let escrowBump: number;
   let vault: anchor.web3.PublicKey;
+  let orderBook: anchor.web3.PublicKey;
 
   const depositAmount = 100;
   const receiveAmount = 200;
 
+  const balance = async (ata: anchor.web3.PublicKey) =>
+    Number((await provider.connection.getTokenAccountBalance(ata)).value.amount);
+
+  const chainTime = async () =>
+    provider.connection.getBlockTime(await provider.connection.getSlot());
+
+  const sleep = (ms: number) => new Promise(resolve => setTimeout(resolve, ms));
+
+  const eventsOf = async (signature: string) => {
+    await provider.connection.confirmTransaction(signature, "confirmed");
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return [...new anchor.EventParser(program.programId, program.coder).parseLogs(tx.meta.logMessages)];
+  };
+
+  // Resolves once the transaction fails with `code`, fails the test if it lands
+  const expectError = async (tx: Promise<unknown>, code: string) => {
+    try {
+      await tx;
+    } catch (err) {
+      expect(err.toString()).to.include(code);
+      return;
+    }
+    expect.fail(`Expected ${code}`);
+  };
+
   before(async () => {
     // Airdrop SOL to maker and taker
     await provider.connection.requestAirdrop(maker, 10 * anchor.web3.LAMPORTS_PER_SOL);
@@ -53,6 +82,11 @@ describe("anchor_escrow_q4_25", () => {
     await provider.sendAndConfirm(takerAtaBTx, [taker]);
     await mintTo(provider.connection, taker, mintB, takerAtaB, taker, receiveAmount * 2);
 
+    [orderBook] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("book"), mintA.toBuffer(), mintB.toBuffer()],
+      program.programId
+    );
+
   });
 
   it("Makes and refunds the escrow", async () => {
@@ -73,6 +107,7 @@ describe("anchor_escrow_q4_25", () => {
         makerAtaA: makerAtaA,
         escrow: escrowPda,
         vault: vault,
+        orderBook: orderBook,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -89,20 +124,31 @@ describe("anchor_escrow_q4_25", () => {
     const vaultBalance = (await provider.connection.getTokenAccountBalance(vault)).value.uiAmount;
     expect(vaultBalance).to.equal(depositAmount);
 
+    let book = await program.account.orderBook.fetch(orderBook);
+    expect(book.asks.map(a => a.escrow.toBase58())).to.deep.equal([escrowPda.toBase58()]);
+    expect(book.asks[0].amountA.toNumber()).to.equal(depositAmount);
+    expect(book.asks[0].amountB.toNumber()).to.equal(receiveAmount);
+
     // Refund
-    await program.methods
-      .refund()
-      .accountsStrict({
-        maker: maker,
-        mintA: mintA,
-        makerAtaA: makerAtaA,
-        escrow: escrowPda,
-        vault: vault,
-        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
-        tokenProgram: TOKEN_PROGRAM_ID,
-        systemProgram: anchor.web3.SystemProgram.programId,
-      })
-      .rpc();
+    const refund = (book: anchor.web3.PublicKey | null) =>
+      program.methods
+        .refund()
+        .accountsStrict({
+          maker: maker,
+          mintA: mintA,
+          makerAtaA: makerAtaA,
+          escrow: escrowPda,
+          vault: vault,
+          orderBook: book,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    // A listed ask can't be closed behind the book's back
+    await expectError(refund(null), "MissingOrderBook");
+    await refund(orderBook);
 
     // Check closed
     const escrowInfo = await provider.connection.getAccountInfo(escrowPda);
@@ -110,6 +156,9 @@ describe("anchor_escrow_q4_25", () => {
 
     const vaultInfo = await provider.connection.getAccountInfo(vault);
     expect(vaultInfo).to.be.null;
+
+    book = await program.account.orderBook.fetch(orderBook);
+    expect(book.asks).to.be.empty;
   });
 
   it("Makes and takes the escrow", async () => {
@@ -130,6 +179,7 @@ describe("anchor_escrow_q4_25", () => {
         makerAtaA: makerAtaA,
         escrow: escrowPda,
         vault: vault,
+        orderBook: orderBook,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -142,7 +192,7 @@ describe("anchor_escrow_q4_25", () => {
 
     // Take
     await program.methods
-      .take()
+      .take(new anchor.BN(receiveAmount), new anchor.BN(depositAmount))
       .accountsStrict({
         taker: taker.publicKey,
         maker: maker,
@@ -153,6 +203,7 @@ describe("anchor_escrow_q4_25", () => {
         makerAtaB: makerAtaB,
         escrow: escrowPda,
         vault: vault,
+        orderBook: orderBook,
         associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: anchor.web3.SystemProgram.programId,
@@ -174,4 +225,864 @@ describe("anchor_escrow_q4_25", () => {
     const makerBalanceB = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
     expect(makerBalanceB).to.equal(receiveAmount);
   });
-});
\ No newline at end of file
+
+  it("Makes and fills a bid", async () => {
+    // The taker holds Token B, so they post the bid and the maker fills it
+    const seed3 = new anchor.BN(3333);
+    const bidDeposit = 50;
+    const bidReceive = 25;
+    const [bidPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), taker.publicKey.toBuffer(), seed3.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const bidVault = getAssociatedTokenAddressSync(mintB, bidPda, true);
+
+    const makeBid = (deposit: number, receive: number) =>
+      program.methods
+        .makeBid(seed3, new anchor.BN(deposit), new anchor.BN(receive))
+        .accountsStrict({
+          maker: taker.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaB: takerAtaB,
+          escrow: bidPda,
+          vault: bidVault,
+          orderBook: orderBook,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+
+    // A zero amount would top the book at an infinite price
+    await expectError(makeBid(0, bidReceive), "InvalidAmount");
+    await expectError(makeBid(bidDeposit, 0), "InvalidAmount");
+    await makeBid(bidDeposit, bidReceive);
+
+    const bidAccount = await program.account.escrow.fetch(bidPda);
+    expect(bidAccount.side).to.deep.equal({ bid: {} });
+    expect(bidAccount.receive.toNumber()).to.equal(bidReceive);
+
+    const vaultBalance = (await provider.connection.getTokenAccountBalance(bidVault)).value.uiAmount;
+    expect(vaultBalance).to.equal(bidDeposit);
+
+    const takerBalanceABefore = (await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount;
+    const makerBalanceBBefore = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+
+    await program.methods
+      .fillBid(new anchor.BN(bidReceive), new anchor.BN(bidDeposit))
+      .accountsStrict({
+        taker: maker,
+        maker: taker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        takerAtaA: makerAtaA,
+        takerAtaB: makerAtaB,
+        makerAtaA: takerAtaA,
+        escrow: bidPda,
+        vault: bidVault,
+        orderBook: orderBook,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+
+    // Check closed
+    const escrowInfo = await provider.connection.getAccountInfo(bidPda);
+    expect(escrowInfo).to.be.null;
+
+    const vaultInfo = await provider.connection.getAccountInfo(bidVault);
+    expect(vaultInfo).to.be.null;
+
+    // Check balances
+    const takerBalanceA = (await provider.connection.getTokenAccountBalance(takerAtaA)).value.uiAmount;
+    expect(takerBalanceA).to.equal(takerBalanceABefore + bidReceive);
+
+    const makerBalanceB = (await provider.connection.getTokenAccountBalance(makerAtaB)).value.uiAmount;
+    expect(makerBalanceB).to.equal(makerBalanceBBefore + bidDeposit);
+  });
+
+  it("Runs an auction with outbid refunds, anti-sniping and settle", async () => {
+    const auctionSeed = new anchor.BN(5555);
+    const lot = 10;
+    const [auctionPda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("auction"), maker.toBuffer(), auctionSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const auctionVault = getAssociatedTokenAddressSync(mintA, auctionPda, true);
+    const bidPdaOf = (bidder: anchor.web3.PublicKey) =>
+      anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("bid"), auctionPda.toBuffer(), bidder.toBuffer()],
+        program.programId
+      )[0];
+
+    const bidder2 = anchor.web3.Keypair.generate();
+    await provider.connection.requestAirdrop(bidder2.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+    await sleep(1000);
+    const bidder2AtaB = getAssociatedTokenAddressSync(mintB, bidder2.publicKey);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(maker, bidder2AtaB, bidder2.publicKey, mintB)
+      )
+    );
+    await mintTo(provider.connection, taker, mintB, bidder2AtaB, taker, 100);
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, lot);
+
+    const start = await chainTime();
+    const endTime = start + 4;
+    const extensionWindow = 8;
+    await program.methods
+      .makeAuction(auctionSeed, new anchor.BN(lot), new anchor.BN(10), new anchor.BN(5), new anchor.BN(endTime), new anchor.BN(extensionWindow))
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        auction: auctionPda,
+        vault: auctionVault,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    expect(await balance(auctionVault)).to.equal(lot);
+
+    const bidAccounts = (
+      bidder: anchor.web3.PublicKey,
+      bidderAtaB: anchor.web3.PublicKey,
+      previousBidder: anchor.web3.PublicKey | null
+    ) => ({
+      bidder: bidder,
+      auction: auctionPda,
+      mintB: mintB,
+      bidderAtaB: bidderAtaB,
+      bid: bidPdaOf(bidder),
+      bidVault: getAssociatedTokenAddressSync(mintB, bidPdaOf(bidder), true),
+      previousBidder: previousBidder,
+      previousBid: previousBidder && bidPdaOf(previousBidder),
+      previousBidVault: previousBidder && getAssociatedTokenAddressSync(mintB, bidPdaOf(previousBidder), true),
+      previousBidderAtaB: previousBidder && getAssociatedTokenAddressSync(mintB, previousBidder),
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    });
+
+    // First bid at the reserve, close enough to the end to extend it
+    await program.methods
+      .placeBid(new anchor.BN(10))
+      .accountsStrict(bidAccounts(taker.publicKey, takerAtaB, null))
+      .signers([taker])
+      .rpc();
+    let auction = await program.account.auction.fetch(auctionPda);
+    expect(auction.highestBidder.toBase58()).to.equal(taker.publicKey.toBase58());
+    expect(auction.highestBid.toNumber()).to.equal(10);
+    expect(auction.endTime.toNumber()).to.be.greaterThan(endTime);
+
+    // Raising our own bid must not pass it as the outbid one
+    await expectError(
+      program.methods
+        .placeBid(new anchor.BN(15))
+        .accountsStrict(bidAccounts(taker.publicKey, takerAtaB, taker.publicKey))
+        .signers([taker])
+        .rpc(),
+      "InvalidPreviousBid"
+    );
+    await program.methods
+      .placeBid(new anchor.BN(15))
+      .accountsStrict(bidAccounts(taker.publicKey, takerAtaB, null))
+      .signers([taker])
+      .rpc();
+    const takerBidVault = getAssociatedTokenAddressSync(mintB, bidPdaOf(taker.publicKey), true);
+    expect(await balance(takerBidVault)).to.equal(15);
+
+    await expectError(
+      program.methods
+        .placeBid(new anchor.BN(19))
+        .accountsStrict(bidAccounts(bidder2.publicKey, bidder2AtaB, taker.publicKey))
+        .signers([bidder2])
+        .rpc(),
+      "BidTooLow"
+    );
+
+    // Outbidding refunds the taker in full and closes their bid
+    const takerBalanceB = await balance(takerAtaB);
+    await program.methods
+      .placeBid(new anchor.BN(20))
+      .accountsStrict(bidAccounts(bidder2.publicKey, bidder2AtaB, taker.publicKey))
+      .signers([bidder2])
+      .rpc();
+    expect(await balance(takerAtaB)).to.equal(takerBalanceB + 15);
+    expect(await provider.connection.getAccountInfo(bidPdaOf(taker.publicKey))).to.be.null;
+    expect(await provider.connection.getAccountInfo(takerBidVault)).to.be.null;
+
+    auction = await program.account.auction.fetch(auctionPda);
+    expect(auction.highestBidder.toBase58()).to.equal(bidder2.publicKey.toBase58());
+
+    const settleAccounts = {
+      settler: maker,
+      maker: maker,
+      auction: auctionPda,
+      mintA: mintA,
+      mintB: mintB,
+      vault: auctionVault,
+      makerAtaA: null,
+      winner: bidder2.publicKey,
+      winningBid: bidPdaOf(bidder2.publicKey),
+      bidVault: getAssociatedTokenAddressSync(mintB, bidPdaOf(bidder2.publicKey), true),
+      winnerAtaA: getAssociatedTokenAddressSync(mintA, bidder2.publicKey),
+      makerAtaB: makerAtaB,
+      associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+    await expectError(
+      program.methods.settle().accountsStrict(settleAccounts).rpc(),
+      "AuctionNotEnded"
+    );
+
+    while ((await chainTime()) <= auction.endTime.toNumber()) {
+      await sleep(1000);
+    }
+
+    const makerBalanceB = await balance(makerAtaB);
+    await program.methods.settle().accountsStrict(settleAccounts).rpc();
+
+    expect(await balance(settleAccounts.winnerAtaA)).to.equal(lot);
+    expect(await balance(makerAtaB)).to.equal(makerBalanceB + 20);
+    expect(await provider.connection.getAccountInfo(auctionPda)).to.be.null;
+    expect(await provider.connection.getAccountInfo(auctionVault)).to.be.null;
+  });
+
+  it("Settles a disputed arbitrated escrow", async () => {
+    const arbitratedSeed = new anchor.BN(6666);
+    const deposit = 40;
+    const receive = 40;
+    const [escrow] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), arbitratedSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const escrowVault = getAssociatedTokenAddressSync(mintA, escrow, true);
+    const escrowVaultB = getAssociatedTokenAddressSync(mintB, escrow, true);
+
+    const arbiter = anchor.web3.Keypair.generate();
+    await provider.connection.requestAirdrop(arbiter.publicKey, 10 * anchor.web3.LAMPORTS_PER_SOL);
+    await sleep(1000);
+    const arbiterAtaA = getAssociatedTokenAddressSync(mintA, arbiter.publicKey);
+    const arbiterAtaB = getAssociatedTokenAddressSync(mintB, arbiter.publicKey);
+    await provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(
+        createAssociatedTokenAccountInstruction(maker, arbiterAtaB, arbiter.publicKey, mintB)
+      )
+    );
+    await mintTo(provider.connection, taker, mintB, arbiterAtaB, taker, receive);
+    await mintTo(provider.connection, taker, mintB, takerAtaB, taker, receive);
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, deposit);
+
+    const makeArbitrated = (
+      arbiterKey: anchor.web3.PublicKey,
+      feeBps: number,
+      amounts: [number, number] = [deposit, receive]
+    ) =>
+      program.methods
+        .makeArbitrated(arbitratedSeed, new anchor.BN(amounts[0]), new anchor.BN(amounts[1]), feeBps)
+        .accountsStrict({
+          maker: maker,
+          arbiter: arbiterKey,
+          mintA: mintA,
+          mintB: mintB,
+          makerAtaA: makerAtaA,
+          escrow: escrow,
+          vault: escrowVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    // The maker can't arbitrate their own trade, nor take more than a 10% fee
+    await expectError(makeArbitrated(maker, 500), "InvalidArbiter");
+    await expectError(makeArbitrated(arbiter.publicKey, 2_000), "ArbiterFeeTooHigh");
+    await expectError(makeArbitrated(arbiter.publicKey, 500, [0, receive]), "InvalidAmount");
+    await expectError(makeArbitrated(arbiter.publicKey, 500, [deposit, 0]), "InvalidAmount");
+    await makeArbitrated(arbiter.publicKey, 500);
+
+    const fund = (funder: anchor.web3.Keypair, funderAtaB: anchor.web3.PublicKey) =>
+      program.methods
+        .fundEscrow()
+        .accountsStrict({
+          taker: funder.publicKey,
+          maker: maker,
+          mintB: mintB,
+          takerAtaB: funderAtaB,
+          escrow: escrow,
+          vaultB: escrowVaultB,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([funder])
+        .rpc();
+
+    await expectError(fund(arbiter, arbiterAtaB), "InvalidArbiter");
+    await fund(taker, takerAtaB);
+    expect(await balance(escrowVaultB)).to.equal(receive);
+
+    await program.methods
+      .raiseDispute()
+      .accountsStrict({ signer: taker.publicKey, escrow: escrow })
+      .signers([taker])
+      .rpc();
+    expect((await program.account.escrow.fetch(escrow)).status).to.deep.equal({ disputed: {} });
+
+    const makerBalanceA = await balance(makerAtaA);
+    const makerBalanceB = await balance(makerAtaB);
+    const takerBalanceA = await balance(takerAtaA);
+    const takerBalanceB = await balance(takerAtaB);
+
+    // Half the trade goes through, each side pays half of the 5% fee
+    await program.methods
+      .resolveDispute(5_000)
+      .accountsStrict({
+        arbiter: arbiter.publicKey,
+        maker: maker,
+        taker: taker.publicKey,
+        mintA: mintA,
+        mintB: mintB,
+        escrow: escrow,
+        vault: escrowVault,
+        vaultB: escrowVaultB,
+        arbiterAtaA: arbiterAtaA,
+        makerAtaA: makerAtaA,
+        makerAtaB: makerAtaB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([arbiter])
+      .rpc();
+
+    expect(await balance(arbiterAtaA)).to.equal(2);
+    expect(await balance(makerAtaB)).to.equal(makerBalanceB + 20);
+    expect(await balance(takerAtaB)).to.equal(takerBalanceB + 20);
+    expect(await balance(makerAtaA)).to.equal(makerBalanceA + 19);
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 19);
+    expect(await provider.connection.getAccountInfo(escrow)).to.be.null;
+  });
+
+  it("Releases milestones one by one and refunds the rest after the deadline", async () => {
+    const milestoneSeed = new anchor.BN(7777);
+    const [escrow] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), milestoneSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const escrowVault = getAssociatedTokenAddressSync(mintA, escrow, true);
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, 12);
+
+    const makeMilestone = (amounts: number[], deadline: number) =>
+      program.methods
+        .makeMilestone(milestoneSeed, amounts.map(amount => new anchor.BN(amount)), new anchor.BN(deadline))
+        .accountsStrict({
+          maker: maker,
+          payee: taker.publicKey,
+          mintA: mintA,
+          makerAtaA: makerAtaA,
+          escrow: escrow,
+          vault: escrowVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    const deadline = (await chainTime()) + 3;
+    await expectError(makeMilestone([5, 0], deadline), "InvalidMilestones");
+    await makeMilestone([5, 7], deadline);
+    expect(await balance(escrowVault)).to.equal(12);
+
+    const approve = (index: number) =>
+      program.methods
+        .approveMilestone(index)
+        .accountsStrict({
+          maker: maker,
+          payee: taker.publicKey,
+          mintA: mintA,
+          escrow: escrow,
+          vault: escrowVault,
+          payeeAtaA: takerAtaA,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    const takerBalanceA = await balance(takerAtaA);
+    await approve(0);
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 5);
+    const escrowAccount = await program.account.escrow.fetch(escrow);
+    expect(escrowAccount.milestones.map(m => m.status)).to.deep.equal([{ released: {} }, { pending: {} }]);
+    await expectError(approve(0), "MilestoneNotPending");
+
+    const refund = () =>
+      program.methods
+        .refundMilestones()
+        .accountsStrict({
+          maker: maker,
+          mintA: mintA,
+          makerAtaA: makerAtaA,
+          escrow: escrow,
+          vault: escrowVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await expectError(refund(), "DeadlineNotReached");
+    while ((await chainTime()) <= deadline) {
+      await sleep(1000);
+    }
+
+    // A token sent straight to the vault doesn't block the close
+    await mintTo(provider.connection, provider.wallet.payer, mintA, escrowVault, provider.wallet.payer, 1);
+
+    const makerBalanceA = await balance(makerAtaA);
+    await refund();
+    expect(await balance(makerAtaA)).to.equal(makerBalanceA + 8);
+    expect(await provider.connection.getAccountInfo(escrow)).to.be.null;
+    expect(await provider.connection.getAccountInfo(escrowVault)).to.be.null;
+  });
+
+  // Lists a plain ask from the maker, funding it first
+  const makeAsk = async (askSeed: anchor.BN, deposit: number, receive: number) => {
+    const [escrow] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("escrow"), maker.toBuffer(), askSeed.toArrayLike(Buffer, "le", 8)],
+      program.programId
+    );
+    const escrowVault = getAssociatedTokenAddressSync(mintA, escrow, true);
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, deposit);
+    const signature = await program.methods
+      .make(askSeed, new anchor.BN(deposit), new anchor.BN(receive))
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        mintB: mintB,
+        makerAtaA: makerAtaA,
+        escrow: escrow,
+        vault: escrowVault,
+        orderBook: orderBook,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    return { escrow, vault: escrowVault, signature };
+  };
+
+  const takeManyGroup = (ask: { escrow: anchor.web3.PublicKey; vault: anchor.web3.PublicKey }) => [
+    { pubkey: ask.escrow, isSigner: false, isWritable: true },
+    { pubkey: ask.vault, isSigner: false, isWritable: true },
+    { pubkey: maker, isSigner: false, isWritable: false },
+    { pubkey: makerAtaB, isSigner: false, isWritable: true },
+  ];
+
+  it("Takes several asks at once within the taker's limit", async () => {
+    const first = await makeAsk(new anchor.BN(8881), 10, 20);
+    const second = await makeAsk(new anchor.BN(8882), 10, 30);
+    await mintTo(provider.connection, taker, mintB, takerAtaB, taker, 50);
+
+    const takeMany = (maxTotalReceive: number) =>
+      program.methods
+        .takeMany(new anchor.BN(maxTotalReceive), [
+          { expectedReceive: new anchor.BN(20), minAmountA: new anchor.BN(10) },
+          { expectedReceive: new anchor.BN(30), minAmountA: new anchor.BN(10) },
+        ])
+        .accountsStrict({
+          taker: taker.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          takerAtaA: takerAtaA,
+          takerAtaB: takerAtaB,
+          orderBook: orderBook,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts([...takeManyGroup(first), ...takeManyGroup(second)])
+        .signers([taker])
+        .rpc();
+
+    await expectError(takeMany(49), "TotalReceiveExceeded");
+
+    const takerBalanceA = await balance(takerAtaA);
+    const makerBalanceB = await balance(makerAtaB);
+    await takeMany(50);
+
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 20);
+    expect(await balance(makerAtaB)).to.equal(makerBalanceB + 50);
+    for (const ask of [first, second]) {
+      expect(await provider.connection.getAccountInfo(ask.escrow)).to.be.null;
+      expect(await provider.connection.getAccountInfo(ask.vault)).to.be.null;
+    }
+    const book = await program.account.orderBook.fetch(orderBook);
+    expect(book.asks).to.be.empty;
+  });
+
+  it("Amends an ask and rejects takes quoted on the old terms", async () => {
+    const ask = await makeAsk(new anchor.BN(9991), 10, 20);
+    await mintTo(provider.connection, taker, mintB, takerAtaB, taker, 30);
+
+    const update = (receive: number, topUp: number, withdraw: number) =>
+      program.methods
+        .updateEscrow(new anchor.BN(receive), new anchor.BN(topUp), new anchor.BN(withdraw))
+        .accountsStrict({
+          maker: maker,
+          depositMint: mintA,
+          makerAta: makerAtaA,
+          escrow: ask.escrow,
+          vault: ask.vault,
+          orderBook: orderBook,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    const take = (expectedReceive: number, minAmountA: number) =>
+      program.methods
+        .take(new anchor.BN(expectedReceive), new anchor.BN(minAmountA))
+        .accountsStrict({
+          taker: taker.publicKey,
+          maker: maker,
+          mintA: mintA,
+          mintB: mintB,
+          takerAtaA: takerAtaA,
+          takerAtaB: takerAtaB,
+          makerAtaB: makerAtaB,
+          escrow: ask.escrow,
+          vault: ask.vault,
+          orderBook: orderBook,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+
+    await expectError(update(0, 0, 0), "InvalidAmount");
+    await expectError(
+      program.methods
+        .updateEscrow(new anchor.BN(30), new anchor.BN(0), new anchor.BN(0))
+        .accountsStrict({
+          maker: maker,
+          depositMint: mintA,
+          makerAta: makerAtaA,
+          escrow: ask.escrow,
+          vault: ask.vault,
+          orderBook: null,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc(),
+      "MissingOrderBook"
+    );
+
+    // The maker raises the price and pulls most of the deposit
+    await update(30, 0, 9);
+    let escrowAccount = await program.account.escrow.fetch(ask.escrow);
+    expect(escrowAccount.receive.toNumber()).to.equal(30);
+    expect(await balance(ask.vault)).to.equal(1);
+    let book = await program.account.orderBook.fetch(orderBook);
+    expect(book.asks[0].amountA.toNumber()).to.equal(1);
+    expect(book.asks[0].amountB.toNumber()).to.equal(30);
+
+    // A taker who quoted 20 for 10 is not filled at 30 for 1
+    await expectError(take(20, 10), "TermsChanged");
+    await expectError(take(30, 10), "TermsChanged");
+
+    await update(20, 9, 0);
+    escrowAccount = await program.account.escrow.fetch(ask.escrow);
+    expect(escrowAccount.receive.toNumber()).to.equal(20);
+
+    const takerBalanceA = await balance(takerAtaA);
+    await take(20, 10);
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 10);
+    book = await program.account.orderBook.fetch(orderBook);
+    expect(book.asks).to.be.empty;
+  });
+
+  it("Vests grants, claims them and revokes the unvested part", async () => {
+    const grant = async (grantSeed: anchor.BN, start: number, revocable: boolean) => {
+      const [vesting] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("vesting"), maker.toBuffer(), grantSeed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const grantVault = getAssociatedTokenAddressSync(mintA, vesting, true);
+      const makeVesting = (cliff: number) =>
+        program.methods
+          .makeVesting(grantSeed, {
+            amount: new anchor.BN(100),
+            start: new anchor.BN(start),
+            cliff: new anchor.BN(cliff),
+            duration: new anchor.BN(100),
+            revocable,
+          })
+          .accountsStrict({
+            grantor: maker,
+            beneficiary: taker.publicKey,
+            mint: mintA,
+            grantorAta: makerAtaA,
+            vesting: vesting,
+            vault: grantVault,
+            associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .rpc();
+      return { vesting, vault: grantVault, makeVesting };
+    };
+    const claim = (vesting: anchor.web3.PublicKey, grantVault: anchor.web3.PublicKey) =>
+      program.methods
+        .claimVested()
+        .accountsStrict({
+          beneficiary: taker.publicKey,
+          grantor: maker,
+          mint: mintA,
+          vesting: vesting,
+          vault: grantVault,
+          beneficiaryAta: takerAtaA,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker])
+        .rpc();
+
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, 200);
+    const now = await chainTime();
+
+    // Started long enough ago to be fully vested
+    const vested = await grant(new anchor.BN(10_001), now - 1_000, false);
+    await expectError(vested.makeVesting(101), "InvalidSchedule");
+    await vested.makeVesting(10);
+    // A token sent straight to the vault goes out with the last tranche
+    await mintTo(provider.connection, provider.wallet.payer, mintA, vested.vault, provider.wallet.payer, 1);
+
+    const takerBalanceA = await balance(takerAtaA);
+    await claim(vested.vesting, vested.vault);
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 101);
+    expect(await provider.connection.getAccountInfo(vested.vesting)).to.be.null;
+    expect(await provider.connection.getAccountInfo(vested.vault)).to.be.null;
+
+    // Not started yet, so revoking hands everything back to the grantor
+    const unvested = await grant(new anchor.BN(10_002), now + 1_000, true);
+    await unvested.makeVesting(10);
+    await expectError(claim(unvested.vesting, unvested.vault), "NothingToClaim");
+
+    const makerBalanceA = await balance(makerAtaA);
+    await program.methods
+      .revoke()
+      .accountsStrict({
+        grantor: maker,
+        beneficiary: taker.publicKey,
+        mint: mintA,
+        grantorAta: makerAtaA,
+        vesting: unvested.vesting,
+        vault: unvested.vault,
+        beneficiaryAta: takerAtaA,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    expect(await balance(makerAtaA)).to.equal(makerBalanceA + 100);
+    expect(await provider.connection.getAccountInfo(unvested.vesting)).to.be.null;
+  });
+
+  it("Releases an oracle escrow on a signed attestation and expires another", async () => {
+    const oracle = anchor.web3.Keypair.generate();
+    const impostor = anchor.web3.Keypair.generate();
+    await mintTo(provider.connection, provider.wallet.payer, mintA, makerAtaA, provider.wallet.payer, 20);
+
+    const makeOracle = async (oracleSeed: anchor.BN, deadline: number) => {
+      const [escrow] = anchor.web3.PublicKey.findProgramAddressSync(
+        [Buffer.from("escrow"), maker.toBuffer(), oracleSeed.toArrayLike(Buffer, "le", 8)],
+        program.programId
+      );
+      const escrowVault = getAssociatedTokenAddressSync(mintA, escrow, true);
+      await program.methods
+        .makeOracle(oracleSeed, new anchor.BN(10), new anchor.BN(deadline))
+        .accountsStrict({
+          maker: maker,
+          taker: taker.publicKey,
+          oracle: oracle.publicKey,
+          mintA: mintA,
+          makerAtaA: makerAtaA,
+          escrow: escrow,
+          vault: escrowVault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      return { escrow, vault: escrowVault };
+    };
+
+    const now = await chainTime();
+    const deadline = now + 1_000;
+    const attested = await makeOracle(new anchor.BN(11_111), deadline);
+
+    // Escrow key, taker and deadline, as in `Escrow::attestation_message`
+    const message = Buffer.concat([
+      attested.escrow.toBuffer(),
+      taker.publicKey.toBuffer(),
+      new anchor.BN(deadline).toArrayLike(Buffer, "le", 8),
+    ]);
+    const release = (signer: anchor.web3.Keypair | null) => {
+      const tx = program.methods
+        .releaseAttested()
+        .accountsStrict({
+          taker: taker.publicKey,
+          maker: maker,
+          mintA: mintA,
+          escrow: attested.escrow,
+          vault: attested.vault,
+          takerAtaA: takerAtaA,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([taker]);
+      if (signer === null) {
+        return tx.rpc();
+      }
+      return tx
+        .preInstructions([
+          anchor.web3.Ed25519Program.createInstructionWithPrivateKey({
+            privateKey: signer.secretKey,
+            message,
+          }),
+        ])
+        .rpc();
+    };
+
+    await expectError(release(null), "MissingAttestation");
+    await expectError(release(impostor), "InvalidAttestation");
+
+    const takerBalanceA = await balance(takerAtaA);
+    await release(oracle);
+    expect(await balance(takerAtaA)).to.equal(takerBalanceA + 10);
+    expect(await provider.connection.getAccountInfo(attested.escrow)).to.be.null;
+
+    // No attestation before the deadline: the maker takes the deposit back
+    const shortDeadline = (await chainTime()) + 3;
+    const unattested = await makeOracle(new anchor.BN(11_112), shortDeadline);
+    const expire = () =>
+      program.methods
+        .expire()
+        .accountsStrict({
+          maker: maker,
+          mintA: mintA,
+          makerAtaA: makerAtaA,
+          escrow: unattested.escrow,
+          vault: unattested.vault,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+
+    await expectError(expire(), "DeadlineNotReached");
+    while ((await chainTime()) <= shortDeadline) {
+      await sleep(1000);
+    }
+
+    const makerBalanceA = await balance(makerAtaA);
+    await expire();
+    expect(await balance(makerAtaA)).to.equal(makerBalanceA + 10);
+    expect(await provider.connection.getAccountInfo(unattested.escrow)).to.be.null;
+  });
+
+  it("Emits make, take and refund events and checks the taker's balance", async () => {
+    const takerBalanceB = await balance(takerAtaB);
+
+    // Priced one unit above what the taker holds
+    const pricey = await makeAsk(new anchor.BN(12_001), 10, takerBalanceB + 1);
+    let [event] = await eventsOf(pricey.signature);
+    expect(event.name).to.equal("makeEvent");
+    expect(event.data.escrow.toBase58()).to.equal(pricey.escrow.toBase58());
+    expect(event.data.deposit.toNumber()).to.equal(10);
+    expect(event.data.receive.toNumber()).to.equal(takerBalanceB + 1);
+
+    await expectError(
+      program.methods
+        .takeMany(new anchor.BN(takerBalanceB + 1), [
+          { expectedReceive: new anchor.BN(takerBalanceB + 1), minAmountA: new anchor.BN(10) },
+        ])
+        .accountsStrict({
+          taker: taker.publicKey,
+          mintA: mintA,
+          mintB: mintB,
+          takerAtaA: takerAtaA,
+          takerAtaB: takerAtaB,
+          orderBook: orderBook,
+          associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .remainingAccounts(takeManyGroup(pricey))
+        .signers([taker])
+        .rpc(),
+      "InsufficientTakerBalance"
+    );
+
+    const refundSignature = await program.methods
+      .refund()
+      .accountsStrict({
+        maker: maker,
+        mintA: mintA,
+        makerAtaA: makerAtaA,
+        escrow: pricey.escrow,
+        vault: pricey.vault,
+        orderBook: orderBook,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .rpc();
+    [event] = await eventsOf(refundSignature);
+    expect(event.name).to.equal("refundEvent");
+    expect(event.data.amount.toNumber()).to.equal(10);
+
+    const cheap = await makeAsk(new anchor.BN(12_002), 10, 5);
+    const takeSignature = await program.methods
+      .take(new anchor.BN(5), new anchor.BN(10))
+      .accountsStrict({
+        taker: taker.publicKey,
+        maker: maker,
+        mintA: mintA,
+        mintB: mintB,
+        takerAtaA: takerAtaA,
+        takerAtaB: takerAtaB,
+        makerAtaB: makerAtaB,
+        escrow: cheap.escrow,
+        vault: cheap.vault,
+        orderBook: orderBook,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([taker])
+      .rpc();
+    [event] = await eventsOf(takeSignature);
+    expect(event.name).to.equal("takeEvent");
+    expect(event.data.taker.toBase58()).to.equal(taker.publicKey.toBase58());
+    expect(event.data.amountA.toNumber()).to.equal(10);
+    expect(event.data.amountB.toNumber()).to.equal(5);
+  });
+});
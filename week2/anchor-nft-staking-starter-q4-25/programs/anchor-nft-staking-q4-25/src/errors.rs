@@ -4,8 +4,8 @@ use anchor_lang::error_code;
 pub enum StakeError {
     #[msg("Max Stake Reached")]
     MaxStakeReached,
-    #[msg("Freeze Time Not Passed")]
-    FreezePeriodNotPassed,
+    #[msg("Lock Period Not Passed")]
+    LockPeriodNotPassed,
     #[msg("Invalid Asset")]
     InvalidAsset,
     #[msg("Asset Not Initialized")]
@@ -22,4 +22,56 @@ pub enum StakeError {
     NotOwner,
     #[msg("No rewards available to claim")]
     NoRewardsToClaim,
+    #[msg("Invalid Stake Account")]
+    InvalidStakeAccount,
+    #[msg("Not Authorized")]
+    NotAuthorized,
+    #[msg("No Pending Admin")]
+    NoPendingAdmin,
+    #[msg("Staking Paused")]
+    StakingPaused,
+    #[msg("Invalid Lock Tiers")]
+    InvalidLockTiers,
+    #[msg("Invalid Lock Tier")]
+    InvalidLockTier,
+    #[msg("Invalid Trait Boosts")]
+    InvalidTraitBoosts,
+    #[msg("Invalid Reward Rate")]
+    InvalidRewardRate,
+    #[msg("User Account Already Migrated")]
+    AlreadyMigrated,
+    #[msg("Invalid Emission Schedule")]
+    InvalidEmissionSchedule,
+    #[msg("Reward Emissions Ended")]
+    EmissionsEnded,
+    #[msg("Reward Emissions Not Ended")]
+    EmissionsNotEnded,
+    #[msg("Reward treasury does not hold enough tokens for this claim")]
+    InsufficientTreasury,
+    #[msg("Invalid Reward Mode")]
+    InvalidRewardMode,
+    #[msg("Missing Treasury")]
+    MissingTreasury,
+    #[msg("Invalid Amount")]
+    InvalidAmount,
+    #[msg("Invalid Unstake Penalty")]
+    InvalidUnstakePenalty,
+    #[msg("Invalid Reward Stream")]
+    InvalidRewardStream,
+    #[msg("Missing Reward Stream Accounts")]
+    MissingStreamAccounts,
+    #[msg("Invalid Max Supply")]
+    InvalidMaxSupply,
+    #[msg("Max Supply Reached")]
+    MaxSupplyReached,
+    #[msg("No Proceeds To Withdraw")]
+    NoProceeds,
+    #[msg("Asset Already Staked")]
+    AssetAlreadyStaked,
+    #[msg("Asset Not Staked")]
+    AssetNotStaked,
+    #[msg("Invalid Streak Tiers")]
+    InvalidStreakTiers,
+    #[msg("Unstake Legacy Assets First")]
+    LegacyStakesOpen,
 }
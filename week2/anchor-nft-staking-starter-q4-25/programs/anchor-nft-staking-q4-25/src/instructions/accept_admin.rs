@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{errors::StakeError, state::StakeConfig};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        let pending_admin = self
+            .config
+            .pending_admin
+            .ok_or(StakeError::NoPendingAdmin)?;
+        require_keys_eq!(
+            pending_admin,
+            self.new_admin.key(),
+            StakeError::NotAuthorized
+        );
+
+        self.config.admin = pending_admin;
+        self.config.pending_admin = None;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+use anchor_lang::{prelude::*, solana_program::program_option::COption};
+use anchor_spl::token::{Mint, Token};
+
+use crate::{
+    errors::StakeError,
+    state::{RewardMode, RewardStream, StakeConfig, MAX_REWARD_STREAMS},
+};
+
+#[derive(Accounts)]
+pub struct AddRewardStream<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ StakeError::NotAuthorized,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> AddRewardStream<'info> {
+    /// Adds a stream paying `reward_per_point` base units of `reward_mint`
+    /// for every point earned between `start` and `end`. A `Mint` stream
+    /// needs the config PDA set as mint authority first. A `Treasury` stream
+    /// pays from the config's ATA for the mint, which is funded with plain
+    /// token transfers. The config's own reward mint can't be streamed.
+    pub fn add_reward_stream(
+        &mut self,
+        mode: RewardMode,
+        reward_per_point: u64,
+        start: i64,
+        end: i64,
+    ) -> Result<()> {
+        // Points already pay out in the reward mint through `claim`, a
+        // stream for it would pay them twice
+        require_keys_neq!(
+            self.reward_mint.key(),
+            self.config.reward_mint,
+            StakeError::InvalidRewardStream
+        );
+        require!(
+            self.config.streams.len() < MAX_REWARD_STREAMS
+                && reward_per_point > 0
+                && start < end
+                && end > Clock::get()?.unix_timestamp,
+            StakeError::InvalidRewardStream
+        );
+        if mode == RewardMode::Mint {
+            require!(
+                self.reward_mint.mint_authority == COption::Some(self.config.key()),
+                StakeError::InvalidRewardStream
+            );
+        }
+
+        self.config.streams.push(RewardStream {
+            mint: self.reward_mint.key(),
+            mode,
+            reward_per_point,
+            start,
+            end,
+        });
+
+        Ok(())
+    }
+}
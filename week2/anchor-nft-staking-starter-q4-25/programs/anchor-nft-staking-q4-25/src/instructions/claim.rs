@@ -1,10 +1,10 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{
-    associated_token::AssociatedToken,
-    token::{mint_to, Mint, MintTo, Token, TokenAccount},
+    associated_token::{get_associated_token_address, AssociatedToken},
+    token::{mint_to, transfer_checked, Mint, MintTo, Token, TokenAccount, TransferChecked},
 };
 
-use crate::state::{StakeConfig, UserAccount};
+use crate::state::{RewardMode, StakeAccount, StakeConfig, UserAccount};
 use crate::errors::StakeError;
 
 #[derive(Accounts)]
@@ -21,21 +21,27 @@ pub struct Claim<'info> {
     )]
     pub rewards_ata: Account<'info, TokenAccount>,
     #[account(
-        seeds = [b"config"],
+        mut,
+        seeds = [b"config", config.collection.as_ref()],
         bump = config.bump
     )]
     pub config: Account<'info, StakeConfig>,
     #[account(
         mut,
-        seeds = [b"rewards", config.key().as_ref()],
-        bump,
-        mint::decimals = 6,
-        mint::authority = config,
+        address = config.reward_mint
     )]
     pub reward_mint: Account<'info, Mint>,
+    // Only passed for pools in `RewardMode::Treasury`
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program
+    )]
+    pub treasury: Option<Account<'info, TokenAccount>>,
     #[account(
         mut,
-        seeds= [b"user", user.key().as_ref()],
+        seeds= [b"user", config.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump
     )]
     pub user_account: Account<'info, UserAccount>,
@@ -45,26 +51,223 @@ pub struct Claim<'info> {
 }
 
 impl<'info> Claim<'info> {
-    pub fn claim(&mut self) -> Result<()>{
+    /// Settles every stake account at the front of `remaining_accounts` up
+    /// to now, then pays out the user's whole balance at the current epoch's
+    /// rate, up to the emission cap, by minting or from the treasury
+    /// depending on the pool's reward mode. Pending reward stream balances
+    /// are paid in the same call. Assets stay staked, so the streak goes on.
+    /// Stakes still locked can't be claimed until `unlock_at`.
+    pub fn claim(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
         //TODO
+        let now = Clock::get()?.unix_timestamp;
+        // Stake accounts first, then one group per reward stream
+        let split = remaining_accounts
+            .iter()
+            .position(|info| info.owner != &crate::ID)
+            .unwrap_or(remaining_accounts.len());
+        let (stake_infos, stream_infos) = remaining_accounts.split_at(split);
+        self.user_account.update_streak(now);
+
+        for stake_info in stake_infos {
+            let points = self.settle_stake(stake_info, now)?;
+            self.user_account.points = self
+                .user_account
+                .points
+                .checked_add(points)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        let paid_streams = self.claim_streams(stream_infos)?;
         let points = self.user_account.points;
+        if paid_streams && (points == 0 || self.config.emissions_finished(now)) {
+            // Nothing left to pay on the main reward, which shouldn't undo
+            // the stream payouts
+            return Ok(());
+        }
+
         require!(points > 0, StakeError::NoRewardsToClaim);
-        let signer_seeds: &[&[&[u8]]] = &[&[b"config", &[self.config.bump]]];
+        require!(
+            !self.config.emissions_finished(now),
+            StakeError::EmissionsEnded
+        );
+        // Points beyond the cap are forfeited, there is nothing left to pay out
+        let amount = self
+            .config
+            .reward_amount(points, self.reward_mint.decimals, now)?;
+        require!(amount > 0, StakeError::NoRewardsToClaim);
+
+        match self.config.reward_mode {
+            RewardMode::Mint => self.mint_reward(&self.reward_mint, &self.rewards_ata, amount)?,
+            RewardMode::Treasury => {
+                let treasury = self.treasury.as_ref().ok_or(StakeError::MissingTreasury)?;
+                require!(treasury.amount >= amount, StakeError::InsufficientTreasury);
+                self.transfer_reward(&self.reward_mint, treasury, &self.rewards_ata, amount)?;
+            }
+        }
+        self.config.emissions.minted += amount;
+        self.user_account.points = 0;
+
+        Ok(())
+    }
+
+    /// Pays every stream with a pending balance. `stream_infos` holds one
+    /// group per `StakeConfig::streams` entry, in order: [mint, user's token
+    /// account], plus the config's ATA for `RewardMode::Treasury` streams.
+    /// Returns whether anything was paid.
+    fn claim_streams(&mut self, stream_infos: &'info [AccountInfo<'info>]) -> Result<bool> {
+        let mut stream_infos = stream_infos.iter();
+        let mut paid = false;
+
+        for (index, stream) in self.config.streams.clone().into_iter().enumerate() {
+            let mut next = || {
+                stream_infos
+                    .next()
+                    .ok_or_else(|| error!(StakeError::MissingStreamAccounts))
+            };
+            let mint_info = next()?;
+            let user_ata_info = next()?;
+            let treasury_info = match stream.mode {
+                RewardMode::Mint => None,
+                RewardMode::Treasury => Some(next()?),
+            };
+
+            let pending = self
+                .user_account
+                .stream_rewards
+                .get(index)
+                .copied()
+                .unwrap_or(0);
+            if pending == 0 {
+                continue;
+            }
+
+            require_keys_eq!(
+                mint_info.key(),
+                stream.mint,
+                StakeError::InvalidRewardStream
+            );
+            let mint = Account::<Mint>::try_from(mint_info)?;
+            let user_ata = Account::<TokenAccount>::try_from(user_ata_info)?;
+            require!(
+                user_ata.mint == stream.mint && user_ata.owner == self.user.key(),
+                StakeError::InvalidRewardStream
+            );
+
+            match treasury_info {
+                None => self.mint_reward(&mint, &user_ata, pending)?,
+                Some(treasury_info) => {
+                    require_keys_eq!(
+                        treasury_info.key(),
+                        get_associated_token_address(&self.config.key(), &stream.mint),
+                        StakeError::InvalidRewardStream
+                    );
+                    let treasury = Account::<TokenAccount>::try_from(treasury_info)?;
+                    require!(treasury.amount >= pending, StakeError::InsufficientTreasury);
+                    self.transfer_reward(&mint, &treasury, &user_ata, pending)?;
+                }
+            }
+
+            self.user_account.stream_rewards[index] = 0;
+            paid = true;
+        }
+
+        Ok(paid)
+    }
+
+    fn mint_reward(
+        &self,
+        mint: &Account<'info, Mint>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"config",
+            self.config.collection.as_ref(),
+            &[self.config.bump],
+        ]];
 
         let cpi_ctx = CpiContext::new_with_signer(
             self.token_program.to_account_info(),
             MintTo {
-                mint: self.reward_mint.to_account_info(),
-                to: self.rewards_ata.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
                 authority: self.config.to_account_info(),
             },
             signer_seeds,
         );
 
-        mint_to(cpi_ctx, points as u64)?;
-        self.user_account.points = 0;
+        mint_to(cpi_ctx, amount)
+    }
 
-        Ok(())
+    fn transfer_reward(
+        &self,
+        mint: &Account<'info, Mint>,
+        treasury: &Account<'info, TokenAccount>,
+        to: &Account<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"config",
+            self.config.collection.as_ref(),
+            &[self.config.bump],
+        ]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            TransferChecked {
+                from: treasury.to_account_info(),
+                mint: mint.to_account_info(),
+                to: to.to_account_info(),
+                authority: self.config.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, amount, mint.decimals)
+    }
+
+    fn settle_stake(&mut self, stake_info: &'info AccountInfo<'info>, now: i64) -> Result<u64> {
+        require!(stake_info.is_writable, StakeError::InvalidStakeAccount);
+        let mut stake_account = Account::<StakeAccount>::try_from(stake_info)?;
+        require_keys_eq!(stake_account.owner, self.user.key(), StakeError::NotOwner);
+        // Locked points only come out through an unstake, so an early one
+        // can't dodge its penalty by claiming first
+        require!(
+            now >= stake_account.unlock_at,
+            StakeError::LockPeriodNotPassed
+        );
+
+        let expected = Pubkey::create_program_address(
+            &[
+                b"stake",
+                self.config.key().as_ref(),
+                stake_account.mint.as_ref(),
+                &[stake_account.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(StakeError::InvalidStakeAccount))?;
+        require_keys_eq!(expected, stake_info.key(), StakeError::InvalidStakeAccount);
+
+        let from = stake_account.last_claimed_at;
+        let (points, penalty_share) = stake_account.settle(now, &self.config)?;
+        self.user_account.accrue_streams(
+            &self.config,
+            points,
+            from,
+            stake_account.last_claimed_at,
+        )?;
+        // Written back right away so a duplicate entry sees the new timestamp
+        stake_account.exit(&crate::ID)?;
+
+        self.config.with_streak_bonus(
+            points
+                .checked_add(penalty_share)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            self.user_account.streak_start,
+            from,
+            stake_account.last_claimed_at,
+        )
     }
 
 }
@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 use mpl_core::{instructions::CreateCollectionV2CpiBuilder, ID as CORE_PROGRAM_ID};
 
 use crate::{errors::StakeError, state::CollectionInfo};
@@ -9,6 +12,8 @@ pub struct CreateCollectionArgs {
     pub uri: String,
     pub nft_name: String,
     pub nft_uri: String,
+    pub max_supply: u32,
+    pub price_lamports: u64,
 }
 
 #[derive(Accounts)]
@@ -31,6 +36,13 @@ pub struct CreateCollection<'info> {
     )]
     pub collection_info: Account<'info, CollectionInfo>,
 
+    #[account(
+        mut,
+        seeds = [b"treasury", collection.key().as_ref()],
+        bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
     #[account(address = CORE_PROGRAM_ID)]
     /// CHECK: Verified by address constraint
     pub core_program: UncheckedAccount<'info>,
@@ -44,6 +56,8 @@ impl<'info> CreateCollection<'info> {
         args: CreateCollectionArgs,
         bumps: &CreateCollectionBumps,
     ) -> Result<()> {
+        require!(args.max_supply > 0, StakeError::InvalidMaxSupply);
+
         self.collection_info.set_inner(CollectionInfo {
             collection: self.collection.key(),
             authority: self.authority.key(),
@@ -51,9 +65,29 @@ impl<'info> CreateCollection<'info> {
             uri: args.uri.clone(),
             nft_name: args.nft_name,
             nft_uri: args.nft_uri,
+            max_supply: args.max_supply,
+            minted: 0,
+            price_lamports: args.price_lamports,
+            treasury: self.treasury.key(),
+            treasury_bump: bumps.treasury,
             bump: bumps.collection_info,
         });
 
+        // Rent exempt from the start, so any mint price can be paid into it
+        let rent = Rent::get()?.minimum_balance(0);
+        if self.treasury.lamports() < rent {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.authority.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                    },
+                ),
+                rent - self.treasury.lamports(),
+            )?;
+        }
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"collection_info",
             &self.collection.key().to_bytes(),
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    state::{StakeAccount, StakeConfig, UserAccount, BPS_DENOMINATOR},
+};
+
+#[derive(Accounts)]
+pub struct EmergencyUnstake<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: collection is verified by the core program
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: asset is verified by the core program
+    #[account(
+        mut,
+        constraint = asset.owner == &CORE_PROGRAM_ID @ StakeError::InvalidAsset,
+        constraint = !asset.data_is_empty() @ StakeError::AssetNotInitialized
+    )]
+    pub asset: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        mut,
+        close = user,
+        constraint = stake_account.owner == user.key() @ StakeError::NotOwner,
+        seeds = [b"stake", config.key().as_ref(), asset.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, StakeAccount>,
+    /// CHECK: verified by the metaplex core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EmergencyUnstake<'info> {
+    /// Unstakes before `unlock_at`. The pool's `early_unstake_penalty_bps`
+    /// share of the points settled here is burned or redistributed to the
+    /// assets still staked, along with this asset's own unvested share of
+    /// earlier penalties. `claim` doesn't settle locked stakes, so that's
+    /// everything earned since staking. Reward streams only accrue on what's
+    /// left after the penalty. Past `unlock_at` there is no penalty, same as
+    /// `unstake`. The streak bonus is applied before the penalty.
+    pub fn emergency_unstake(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let penalty_bps = if now < self.stake_account.unlock_at {
+            self.config.early_unstake_penalty_bps as u128
+        } else {
+            0
+        };
+        let penalty_of =
+            |points: u64| (points as u128 * penalty_bps / BPS_DENOMINATOR as u128) as u64;
+
+        self.user_account.update_streak(now);
+        let from = self.stake_account.last_claimed_at;
+        let (points, penalty_share) = self.stake_account.settle(now, &self.config)?;
+        self.user_account.accrue_streams(
+            &self.config,
+            points - penalty_of(points),
+            from,
+            self.stake_account.last_claimed_at,
+        )?;
+        let points = self.config.with_streak_bonus(
+            points
+                .checked_add(penalty_share)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            self.user_account.streak_start,
+            from,
+            self.stake_account.last_claimed_at,
+        )?;
+        let penalty = penalty_of(points);
+        let forfeited = self.stake_account.unvested_penalty(&self.config);
+
+        self.user_account.points = self
+            .user_account
+            .points
+            .checked_add(points - penalty)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.user_account.amount_staked = self
+            .user_account
+            .amount_staked
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.user_account.end_streak_if_empty();
+        self.config.total_staked = self
+            .config
+            .total_staked
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.config.distribute_penalty(
+            penalty
+                .checked_add(forfeited)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+        )?;
+
+        let signers_seeds: &[&[&[u8]]] = &[&[
+            b"stake",
+            &self.config.key().to_bytes(),
+            &self.asset.key().to_bytes(),
+            &[self.stake_account.bump],
+        ]];
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.stake_account.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signers_seeds)?;
+
+        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .plugin_type(PluginType::FreezeDelegate)
+            .invoke()
+    }
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked};
+
+use crate::{
+    errors::StakeError,
+    state::{RewardMode, StakeConfig},
+};
+
+#[derive(Accounts)]
+pub struct FundRewards<'info> {
+    // Anyone can top up a treasury
+    pub funder: Signer<'info>,
+
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump,
+        constraint = config.reward_mode == RewardMode::Treasury @ StakeError::InvalidRewardMode
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(address = config.reward_mint)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        token::mint = reward_mint,
+        token::authority = funder,
+    )]
+    pub funder_ata: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FundRewards<'info> {
+    pub fn fund_rewards(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, StakeError::InvalidAmount);
+
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.funder_ata.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.treasury.to_account_info(),
+                    authority: self.funder.to_account_info(),
+                },
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )
+    }
+}
@@ -1,17 +1,33 @@
 use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token};
 
-use crate::state::StakeConfig;
+use crate::{
+    errors::StakeError,
+    program::AnchorNftStakingQ425,
+    state::{CollectionInfo, EmissionSchedule, LockTier, PenaltyMode, RewardMode, StakeConfig},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct EmissionArgs {
+    pub max_supply: u64,
+    pub epoch_duration: i64,
+}
 
 #[derive(Accounts)]
 pub struct InitializeConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
 
+    #[account(
+        seeds = [b"collection_info", collection_info.collection.as_ref()],
+        bump = collection_info.bump
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+
     #[account(
         init,
         payer = admin,
-        seeds = [b"config".as_ref()],
+        seeds = [b"config".as_ref(), collection_info.collection.as_ref()],
         bump,
         space = StakeConfig::DISCRIMINATOR.len() + StakeConfig::INIT_SPACE,
     )]
@@ -29,20 +45,53 @@ pub struct InitializeConfig<'info> {
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    #[account(constraint = this_program.programdata_address()? == Some(program_data.key()))]
+    pub this_program: Program<'info, AnchorNftStakingQ425>,
+    // Only the program upgrade authority can open pools
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StakeError::NotAuthorized)]
+    pub program_data: Account<'info, ProgramData>,
 }
 
 impl<'info> InitializeConfig<'info> {
     pub fn initialize_config(
         &mut self,
         points_per_stake: u8,
+        reward_rate_bps: u64,
         max_stake: u8,
-        freeze_period: u32,
+        lock_tiers: Vec<LockTier>,
+        emissions: EmissionArgs,
         bumps: &InitializeConfigBumps,
     ) -> Result<()> {
+        StakeConfig::validate_lock_tiers(&lock_tiers)?;
+        require!(reward_rate_bps > 0, StakeError::InvalidRewardRate);
+        EmissionSchedule::validate(emissions.max_supply, emissions.epoch_duration)?;
+
+        let now = Clock::get()?.unix_timestamp;
         self.config.set_inner(StakeConfig {
+            collection: self.collection_info.collection,
+            admin: self.admin.key(),
+            pending_admin: None,
+            paused: false,
             points_per_stake,
+            reward_rate_bps,
+            reward_mode: RewardMode::Mint,
+            reward_mint: self.reward_mint.key(),
+            emissions: EmissionSchedule {
+                max_supply: emissions.max_supply,
+                minted: 0,
+                start: now,
+                epoch_duration: emissions.epoch_duration,
+            },
             max_stake,
-            freeze_period,
+            total_staked: 0,
+            early_unstake_penalty_bps: 0,
+            penalty_mode: PenaltyMode::Burn,
+            penalty_index: 0,
+            points_index: 0,
+            points_index_updated_at: now,
+            lock_tiers,
+            streams: vec![],
+            streak_tiers: vec![],
             rewards_bump: bumps.reward_mint,
             bump: bumps.config,
         });
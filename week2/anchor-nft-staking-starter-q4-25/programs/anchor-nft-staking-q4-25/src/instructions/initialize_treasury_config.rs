@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token::{Mint, Token, TokenAccount},
+};
+
+use crate::{
+    errors::StakeError,
+    instructions::EmissionArgs,
+    program::AnchorNftStakingQ425,
+    state::{CollectionInfo, EmissionSchedule, LockTier, PenaltyMode, RewardMode, StakeConfig},
+};
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"collection_info", collection_info.collection.as_ref()],
+        bump = collection_info.bump
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+
+    #[account(
+        init,
+        payer = admin,
+        seeds = [b"config".as_ref(), collection_info.collection.as_ref()],
+        bump,
+        space = StakeConfig::DISCRIMINATOR.len() + StakeConfig::INIT_SPACE,
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    // The partner's existing token, the program never needs its mint authority
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: Account<'info, Mint>,
+
+    #[account(
+        init,
+        payer = admin,
+        associated_token::mint = reward_mint,
+        associated_token::authority = config,
+        associated_token::token_program = token_program
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    #[account(constraint = this_program.programdata_address()? == Some(program_data.key()))]
+    pub this_program: Program<'info, AnchorNftStakingQ425>,
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ StakeError::NotAuthorized)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+impl<'info> InitializeTreasuryConfig<'info> {
+    pub fn initialize_treasury_config(
+        &mut self,
+        points_per_stake: u8,
+        reward_rate_bps: u64,
+        max_stake: u8,
+        lock_tiers: Vec<LockTier>,
+        emissions: EmissionArgs,
+        bumps: &InitializeTreasuryConfigBumps,
+    ) -> Result<()> {
+        StakeConfig::validate_lock_tiers(&lock_tiers)?;
+        require!(reward_rate_bps > 0, StakeError::InvalidRewardRate);
+        EmissionSchedule::validate(emissions.max_supply, emissions.epoch_duration)?;
+
+        let now = Clock::get()?.unix_timestamp;
+        self.config.set_inner(StakeConfig {
+            collection: self.collection_info.collection,
+            admin: self.admin.key(),
+            pending_admin: None,
+            paused: false,
+            points_per_stake,
+            reward_rate_bps,
+            reward_mode: RewardMode::Treasury,
+            reward_mint: self.reward_mint.key(),
+            emissions: EmissionSchedule {
+                max_supply: emissions.max_supply,
+                minted: 0,
+                start: now,
+                epoch_duration: emissions.epoch_duration,
+            },
+            max_stake,
+            total_staked: 0,
+            early_unstake_penalty_bps: 0,
+            penalty_mode: PenaltyMode::Burn,
+            penalty_index: 0,
+            points_index: 0,
+            points_index_updated_at: now,
+            lock_tiers,
+            streams: vec![],
+            streak_tiers: vec![],
+            // No rewards PDA in this mode
+            rewards_bump: 0,
+            bump: bumps.config,
+        });
+
+        Ok(())
+    }
+}
@@ -1,16 +1,22 @@
 use anchor_lang::prelude::*;
 
-use crate::state::UserAccount;
+use crate::state::{StakeConfig, UserAccount};
 
 #[derive(Accounts)]
 pub struct Initialize<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
 
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+
     #[account(
         init,
         payer = user,
-        seeds = [b"user".as_ref(), user.key().as_ref()],
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
         bump,
         space = UserAccount::DISCRIMINATOR.len() + UserAccount::INIT_SPACE,
     )]
@@ -22,9 +28,13 @@ pub struct Initialize<'info> {
 impl<'info> Initialize<'info> {
     pub fn initialize_user_account(&mut self, bumps: &InitializeBumps) -> Result<()> {
         self.user_account.set_inner(UserAccount {
+            config: self.config.key(),
             points: 0,
             amount_staked: 0,
+            streak_start: 0,
+            streak_days: 0,
             bump: bumps.user_account,
+            stream_rewards: vec![],
         });
 
         Ok(())
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StakeError,
+    state::{close_legacy, load_legacy, LegacyUserAccount, StakeConfig, UserAccount},
+};
+
+#[derive(Accounts)]
+pub struct MigrateUser<'info> {
+    // Pays the rent for the new account, gets the legacy one's back
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+    /// CHECK: the user's account in the legacy pool, read with `load_legacy`
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump
+    )]
+    pub legacy_user_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = user,
+        seeds = [b"user".as_ref(), config.key().as_ref(), user.key().as_ref()],
+        bump,
+        space = UserAccount::DISCRIMINATOR.len() + UserAccount::INIT_SPACE,
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUser<'info> {
+    /// Moves the points of a user account from the legacy single pool into
+    /// the user's account in `config`, in place of `initialize_user`, and
+    /// closes the legacy account. Anything still staked there has to come
+    /// out through `unstake_legacy` first.
+    pub fn migrate_user(&mut self, bumps: &MigrateUserBumps) -> Result<()> {
+        require!(
+            self.legacy_user_account.owner == &crate::ID,
+            StakeError::AlreadyMigrated
+        );
+        let legacy: LegacyUserAccount =
+            load_legacy::<UserAccount, _>(&self.legacy_user_account, LegacyUserAccount::LEN)?;
+        require!(legacy.amount_staked == 0, StakeError::LegacyStakesOpen);
+
+        self.user_account.set_inner(UserAccount {
+            config: self.config.key(),
+            points: legacy.points as u64,
+            amount_staked: 0,
+            streak_start: 0,
+            streak_days: 0,
+            bump: bumps.user_account,
+            stream_rewards: vec![],
+        });
+
+        close_legacy(&self.legacy_user_account, &self.user)
+    }
+}
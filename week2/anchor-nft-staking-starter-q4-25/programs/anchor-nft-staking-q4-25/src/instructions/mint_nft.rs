@@ -1,4 +1,7 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 use mpl_core::{
     instructions::CreateV2CpiBuilder,
     types::{Attribute, Attributes, Plugin, PluginAuthorityPair},
@@ -27,11 +30,18 @@ pub struct MintNft<'info> {
     pub collection: UncheckedAccount<'info>,
 
     #[account(
+        mut,
         seeds = [b"collection_info", collection.key().as_ref()],
         bump = collection_info.bump,
     )]
     pub collection_info: Account<'info, CollectionInfo>,
 
+    #[account(
+        mut,
+        address = collection_info.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+
     #[account(address = CORE_PROGRAM_ID)]
     /// CHECK: Verified by address constraint
     pub core_program: UncheckedAccount<'info>,
@@ -41,6 +51,24 @@ pub struct MintNft<'info> {
 
 impl<'info> MintNft<'info> {
     pub fn mint_nft(&mut self) -> Result<()> {
+        require!(
+            self.collection_info.minted < self.collection_info.max_supply,
+            StakeError::MaxSupplyReached
+        );
+        if self.collection_info.price_lamports > 0 {
+            transfer(
+                CpiContext::new(
+                    self.system_program.to_account_info(),
+                    Transfer {
+                        from: self.minter.to_account_info(),
+                        to: self.treasury.to_account_info(),
+                    },
+                ),
+                self.collection_info.price_lamports,
+            )?;
+        }
+        self.collection_info.minted += 1;
+
         let signer_seeds: &[&[&[u8]]] = &[&[
             b"collection_info",
             &self.collection.key().to_bytes(),
@@ -16,5 +16,50 @@ pub use stake::*;
 pub mod unstake;
 pub use unstake::*;
 
+pub mod unstake_legacy;
+pub use unstake_legacy::*;
+
 pub mod claim;
 pub use claim::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod set_trait_boosts;
+pub use set_trait_boosts::*;
+
+pub mod stake_many;
+pub use stake_many::*;
+
+pub mod unstake_many;
+pub use unstake_many::*;
+
+pub mod migrate_user;
+pub use migrate_user::*;
+
+pub mod release_mint_authority;
+pub use release_mint_authority::*;
+
+pub mod initialize_treasury_config;
+pub use initialize_treasury_config::*;
+
+pub mod fund_rewards;
+pub use fund_rewards::*;
+
+pub mod emergency_unstake;
+pub use emergency_unstake::*;
+
+pub mod add_reward_stream;
+pub use add_reward_stream::*;
+
+pub mod withdraw_proceeds;
+pub use withdraw_proceeds::*;
+
+pub mod stake_on_asset;
+pub use stake_on_asset::*;
+
+pub mod unstake_on_asset;
+pub use unstake_on_asset::*;
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{
+    set_authority, spl_token::instruction::AuthorityType, Mint, SetAuthority, Token,
+};
+
+use crate::{
+    errors::StakeError,
+    state::{RewardMode, StakeConfig},
+};
+
+#[derive(Accounts)]
+pub struct ReleaseMintAuthority<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ StakeError::NotAuthorized,
+        constraint = config.reward_mode == RewardMode::Mint @ StakeError::InvalidRewardMode,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        mut,
+        seeds = [b"rewards".as_ref(), config.key().as_ref()],
+        bump = config.rewards_bump,
+        mint::authority = config,
+    )]
+    pub reward_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ReleaseMintAuthority<'info> {
+    /// Hands the reward mint to `new_authority` (e.g. a DAO) once the
+    /// emission cap has been minted or the rate has halved to zero. The pool
+    /// can't mint after this.
+    pub fn release_mint_authority(&mut self, new_authority: Pubkey) -> Result<()> {
+        require!(
+            self.config.emissions_finished(Clock::get()?.unix_timestamp),
+            StakeError::EmissionsNotEnded
+        );
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"config",
+            self.config.collection.as_ref(),
+            &[self.config.bump],
+        ]];
+
+        set_authority(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                SetAuthority {
+                    current_authority: self.config.to_account_info(),
+                    account_or_mint: self.reward_mint.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            AuthorityType::MintTokens,
+            Some(new_authority),
+        )
+    }
+}
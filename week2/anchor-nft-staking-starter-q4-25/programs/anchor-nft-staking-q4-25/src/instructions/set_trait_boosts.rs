@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StakeError,
+    state::{BoostTable, StakeConfig, TraitBoost},
+};
+
+#[derive(Accounts)]
+pub struct SetTraitBoosts<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        has_one = admin @ StakeError::NotAuthorized,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = BoostTable::DISCRIMINATOR.len() + BoostTable::INIT_SPACE,
+        seeds = [b"boosts".as_ref(), config.key().as_ref()],
+        bump
+    )]
+    pub boost_table: Account<'info, BoostTable>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetTraitBoosts<'info> {
+    /// Replaces the whole table. Existing stakes keep the boost they were
+    /// staked with.
+    pub fn set_trait_boosts(
+        &mut self,
+        boosts: Vec<TraitBoost>,
+        bumps: &SetTraitBoostsBumps,
+    ) -> Result<()> {
+        BoostTable::validate(&boosts)?;
+
+        self.boost_table.set_inner(BoostTable {
+            config: self.config.key(),
+            boosts,
+            bump: bumps.boost_table,
+        });
+
+        Ok(())
+    }
+}
@@ -1,13 +1,18 @@
 use anchor_lang::prelude::*;
 use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin,
     instructions::AddPluginV1CpiBuilder,
-    types::{FreezeDelegate, Plugin, PluginAuthority},
+    types::{Attributes, FreezeDelegate, Plugin, PluginAuthority, PluginType, UpdateAuthority},
     ID as CORE_PROGRAM_ID,
 };
 
 use crate::{
     errors::StakeError,
-    state::{StakeAccount, StakeConfig, UserAccount},
+    state::{
+        BoostTable, CollectionInfo, StakeAccount, StakeConfig, UserAccount, BPS_DENOMINATOR,
+        SECONDS_PER_DAY,
+    },
 };
 
 #[derive(Accounts)]
@@ -22,6 +27,13 @@ pub struct Stake<'info> {
         constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
     )]
     pub collection: UncheckedAccount<'info>,
+    // Only collections created through `create_collection` can be staked
+    #[account(
+        seeds = [b"collection_info", collection.key().as_ref()],
+        bump = collection_info.bump,
+        constraint = collection_info.collection == collection.key() @ StakeError::InvalidCollection
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
     /// CHECK: metaplex asset
     #[account(
         mut,
@@ -31,15 +43,17 @@ pub struct Stake<'info> {
     pub asset: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds= [b"user", user.key().as_ref()],
-        bump = user_account.bump
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::StakingPaused
     )]
-    pub user_account: Account<'info, UserAccount>,
+    pub config: Account<'info, StakeConfig>,
     #[account(
-        seeds = [b"config".as_ref()],
-        bump = config.bump
+        mut,
+        seeds= [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
     )]
-    pub config: Account<'info, StakeConfig>,
+    pub user_account: Account<'info, UserAccount>,
     #[account(
         init,
         payer = user,
@@ -48,6 +62,12 @@ pub struct Stake<'info> {
         bump
     )]
     pub stake_account: Account<'info, StakeAccount>,
+    // Pools without trait boosts have no table
+    #[account(
+        seeds = [b"boosts".as_ref(), config.key().as_ref()],
+        bump = boost_table.bump
+    )]
+    pub boost_table: Option<Account<'info, BoostTable>>,
     /// CHECK: metaplex core program
     #[account(address=CORE_PROGRAM_ID)]
     pub core_program: UncheckedAccount<'info>,
@@ -55,7 +75,15 @@ pub struct Stake<'info> {
 }
 
 impl<'info> Stake<'info> {
-    pub fn stake(&mut self, bumps: &StakeBumps) -> Result<()> {
+    pub fn stake(&mut self, tier: u8, bumps: &StakeBumps) -> Result<()> {
+        verify_asset(&self.asset, &self.collection.key(), &self.user.key())?;
+        let lock_tier = *self
+            .config
+            .lock_tiers
+            .get(tier as usize)
+            .ok_or(StakeError::InvalidLockTier)?;
+        let boost_bps = trait_boost(&self.asset, self.boost_table.as_deref());
+
         require!(
             self.user_account.amount_staked < self.config.max_stake,
             StakeError::MaxStakeReached
@@ -71,19 +99,63 @@ impl<'info> Stake<'info> {
                 address: self.stake_account.key(),
             })
             .invoke()?;
+        let now = Clock::get()?.unix_timestamp;
         self.stake_account.set_inner(StakeAccount {
             owner: self.user.key(),
             mint: self.asset.key(),
-            staked_at: Clock::get()?.unix_timestamp,
+            staked_at: now,
+            last_claimed_at: now,
+            tier,
+            multiplier_bps: lock_tier.multiplier_bps,
+            boost_bps,
+            unlock_at: now + lock_tier.lock_days as i64 * SECONDS_PER_DAY,
+            penalty_index: self.config.penalty_index,
+            points_index: self.config.points_index_at(now),
             bump: bumps.stake_account,
         });
 
+        self.user_account.start_streak(now);
         self.user_account.amount_staked = self
             .user_account
             .amount_staked
             .checked_add(1)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.config.total_staked = self
+            .config
+            .total_staked
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
 
         Ok(())
     }
 }
+
+/// Checks that `asset` is a Core asset in `collection` owned by `owner`.
+pub fn verify_asset(asset: &AccountInfo, collection: &Pubkey, owner: &Pubkey) -> Result<()> {
+    require_keys_eq!(*asset.owner, CORE_PROGRAM_ID, StakeError::InvalidAsset);
+    let asset_data =
+        BaseAssetV1::from_bytes(&asset.try_borrow_data()?).map_err(|_| StakeError::InvalidAsset)?;
+
+    require!(
+        asset_data.update_authority == UpdateAuthority::Collection(*collection),
+        StakeError::InvalidCollection
+    );
+    require_keys_eq!(asset_data.owner, *owner, StakeError::NotOwner);
+
+    Ok(())
+}
+
+/// 1x unless the asset has a trait listed in the pool's boost table.
+pub fn trait_boost(asset: &AccountInfo, boost_table: Option<&BoostTable>) -> u16 {
+    let no_boost = BPS_DENOMINATOR as u16;
+    let Some(boost_table) = boost_table else {
+        return no_boost;
+    };
+
+    match fetch_plugin::<BaseAssetV1, Attributes>(asset, PluginType::Attributes) {
+        Ok((_, attributes, _)) => boost_table
+            .boost_for(&attributes.attribute_list)
+            .unwrap_or(no_boost),
+        Err(_) => no_boost,
+    }
+}
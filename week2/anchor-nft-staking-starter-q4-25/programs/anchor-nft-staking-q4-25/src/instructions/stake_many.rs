@@ -0,0 +1,216 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{
+        allocate, assign, create_account, transfer, Allocate, Assign, CreateAccount, Transfer,
+    },
+};
+use mpl_core::{
+    instructions::AddPluginV1CpiBuilder,
+    types::{FreezeDelegate, Plugin, PluginAuthority},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    instructions::{trait_boost, verify_asset},
+    state::{BoostTable, CollectionInfo, StakeAccount, StakeConfig, UserAccount, SECONDS_PER_DAY},
+};
+
+// Each asset is passed in `remaining_accounts` as [asset, stake_account]
+pub const STAKE_MANY_GROUP_LEN: usize = 2;
+
+#[derive(Accounts)]
+pub struct StakeMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: metaplex collection
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"collection_info", collection.key().as_ref()],
+        bump = collection_info.bump,
+        constraint = collection_info.collection == collection.key() @ StakeError::InvalidCollection
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::StakingPaused
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds= [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        seeds = [b"boosts".as_ref(), config.key().as_ref()],
+        bump = boost_table.bump
+    )]
+    pub boost_table: Option<Account<'info, BoostTable>>,
+    /// CHECK: metaplex core program
+    #[account(address=CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeMany<'info> {
+    pub fn stake_many(
+        &mut self,
+        remaining_accounts: &'info [AccountInfo<'info>],
+        tier: u8,
+    ) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty() && remaining_accounts.len() % STAKE_MANY_GROUP_LEN == 0,
+            StakeError::InvalidStakeAccount
+        );
+        let count = remaining_accounts.len() / STAKE_MANY_GROUP_LEN;
+        let amount_staked = u8::try_from(count)
+            .ok()
+            .and_then(|count| self.user_account.amount_staked.checked_add(count))
+            .ok_or(StakeError::MaxStakeReached)?;
+        require!(
+            amount_staked <= self.config.max_stake,
+            StakeError::MaxStakeReached
+        );
+
+        let lock_tier = *self
+            .config
+            .lock_tiers
+            .get(tier as usize)
+            .ok_or(StakeError::InvalidLockTier)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        for group in remaining_accounts.chunks(STAKE_MANY_GROUP_LEN) {
+            let (asset, stake_info) = (&group[0], &group[1]);
+            verify_asset(asset, &self.collection.key(), &self.user.key())?;
+
+            let stake_account = StakeAccount {
+                owner: self.user.key(),
+                mint: asset.key(),
+                staked_at: now,
+                last_claimed_at: now,
+                tier,
+                multiplier_bps: lock_tier.multiplier_bps,
+                boost_bps: trait_boost(asset, self.boost_table.as_deref()),
+                unlock_at: now + lock_tier.lock_days as i64 * SECONDS_PER_DAY,
+                penalty_index: self.config.penalty_index,
+                points_index: self.config.points_index_at(now),
+                bump: 0,
+            };
+            self.init_stake_account(stake_info, stake_account)?;
+
+            AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
+                .asset(asset)
+                .collection(Some(&self.collection.to_account_info()))
+                .payer(&self.user.to_account_info())
+                .authority(None)
+                .system_program(&self.system_program.to_account_info())
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+                .init_authority(PluginAuthority::Address {
+                    address: stake_info.key(),
+                })
+                .invoke()?;
+        }
+
+        self.user_account.start_streak(now);
+        self.user_account.amount_staked = amount_staked;
+        self.config.total_staked = self
+            .config
+            .total_staked
+            .checked_add(count as u32)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    // What `init` does for `Stake::stake_account`, for an account that isn't
+    // known until runtime
+    fn init_stake_account(
+        &self,
+        stake_info: &'info AccountInfo<'info>,
+        mut stake_account: StakeAccount,
+    ) -> Result<()> {
+        let config_key = self.config.key();
+        let (expected, bump) = Pubkey::find_program_address(
+            &[b"stake", config_key.as_ref(), stake_account.mint.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(expected, stake_info.key(), StakeError::InvalidStakeAccount);
+        require!(
+            stake_info.is_writable && stake_info.data_is_empty(),
+            StakeError::InvalidStakeAccount
+        );
+        stake_account.bump = bump;
+
+        let space = StakeAccount::DISCRIMINATOR.len() + StakeAccount::INIT_SPACE;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"stake",
+            config_key.as_ref(),
+            stake_account.mint.as_ref(),
+            &[bump],
+        ]];
+        let rent = Rent::get()?.minimum_balance(space);
+        let system_program = self.system_program.to_account_info();
+        if stake_info.lamports() == 0 {
+            create_account(
+                CpiContext::new_with_signer(
+                    system_program,
+                    CreateAccount {
+                        from: self.user.to_account_info(),
+                        to: stake_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rent,
+                space as u64,
+                &crate::ID,
+            )?;
+        } else {
+            // Someone sent lamports to the PDA first, which `create_account`
+            // refuses. Top it up and claim it the way `init` does
+            let top_up = rent.saturating_sub(stake_info.lamports());
+            if top_up > 0 {
+                transfer(
+                    CpiContext::new(
+                        system_program.clone(),
+                        Transfer {
+                            from: self.user.to_account_info(),
+                            to: stake_info.clone(),
+                        },
+                    ),
+                    top_up,
+                )?;
+            }
+            allocate(
+                CpiContext::new_with_signer(
+                    system_program.clone(),
+                    Allocate {
+                        account_to_allocate: stake_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                space as u64,
+            )?;
+            assign(
+                CpiContext::new_with_signer(
+                    system_program,
+                    Assign {
+                        account_to_assign: stake_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                &crate::ID,
+            )?;
+        }
+
+        let mut data = stake_info.try_borrow_mut_data()?;
+        stake_account.try_serialize(&mut &mut data[..])
+    }
+}
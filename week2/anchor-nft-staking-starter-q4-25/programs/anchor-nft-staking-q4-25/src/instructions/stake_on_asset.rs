@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    accounts::BaseAssetV1,
+    fetch_plugin,
+    instructions::{
+        AddPluginV1CpiBuilder, ApprovePluginAuthorityV1CpiBuilder, UpdatePluginV1CpiBuilder,
+    },
+    types::{Attribute, Attributes, FreezeDelegate, Plugin, PluginAuthority, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    instructions::verify_asset,
+    state::{CollectionInfo, StakeConfig, UserAccount},
+};
+
+// Attribute keys written to staked assets. Values are unix seconds, and
+// `staked_at` is 0 while the asset isn't staked
+pub const STAKED_AT_KEY: &str = "staked_at";
+pub const STAKED_TIME_KEY: &str = "staked_time";
+// `StakeConfig::points_index` at `staked_at`, 0 while the asset isn't staked
+pub const POINTS_INDEX_KEY: &str = "points_index";
+
+#[derive(Accounts)]
+pub struct StakeOnAsset<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: metaplex collection
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    // Update authority of the collection, so it can hand the asset's
+    // attributes to the config PDA
+    #[account(
+        seeds = [b"collection_info", collection.key().as_ref()],
+        bump = collection_info.bump,
+        constraint = collection_info.collection == collection.key() @ StakeError::InvalidCollection
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+    /// CHECK: metaplex asset
+    #[account(
+        mut,
+        constraint = asset.owner == &CORE_PROGRAM_ID @ StakeError::InvalidAsset,
+        constraint = !asset.data_is_empty() @ StakeError::AssetNotInitialized
+    )]
+    pub asset: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump,
+        constraint = !config.paused @ StakeError::StakingPaused
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    /// CHECK: metaplex core program
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> StakeOnAsset<'info> {
+    /// Stakes without a `StakeAccount`: the stake time goes into the asset's
+    /// `Attributes` plugin and the config PDA becomes the authority of that
+    /// plugin and the freeze authority.
+    /// Points accrue at the base rate with the trait boost and are credited
+    /// by `unstake_on_asset`. There are no lock tiers in this mode.
+    pub fn stake_on_asset(&mut self) -> Result<()> {
+        verify_asset(&self.asset, &self.collection.key(), &self.user.key())?;
+        require!(
+            self.user_account.amount_staked < self.config.max_stake,
+            StakeError::MaxStakeReached
+        );
+
+        let (mut attributes, plugin_authority) = asset_attributes(&self.asset);
+        require!(
+            attribute_value(&attributes, STAKED_AT_KEY) == 0,
+            StakeError::AssetAlreadyStaked
+        );
+        let now = Clock::get()?.unix_timestamp;
+        set_attribute(&mut attributes, STAKED_AT_KEY, now);
+        let points_index = i64::try_from(self.config.points_index_at(now))
+            .map_err(|_| ProgramError::ArithmeticOverflow)?;
+        set_attribute(&mut attributes, POINTS_INDEX_KEY, points_index);
+        // Carried over from earlier stakes, shown as 0 on the first one
+        let staked_time = attribute_value(&attributes, STAKED_TIME_KEY);
+        set_attribute(&mut attributes, STAKED_TIME_KEY, staked_time);
+
+        let config_authority = PluginAuthority::Address {
+            address: self.config.key(),
+        };
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"collection_info",
+            self.collection_info.collection.as_ref(),
+            &[self.collection_info.bump],
+        ]];
+        match plugin_authority {
+            None => {
+                AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
+                    .asset(&self.asset.to_account_info())
+                    .collection(Some(&self.collection.to_account_info()))
+                    .payer(&self.user.to_account_info())
+                    .authority(Some(&self.collection_info.to_account_info()))
+                    .system_program(&self.system_program.to_account_info())
+                    .plugin(Plugin::Attributes(Attributes {
+                        attribute_list: attributes,
+                    }))
+                    .init_authority(config_authority)
+                    .invoke_signed(signer_seeds)?;
+            }
+            Some(authority) => {
+                // `mint_nft` adds the plugin under the update authority
+                if authority != config_authority {
+                    ApprovePluginAuthorityV1CpiBuilder::new(&self.core_program.to_account_info())
+                        .asset(&self.asset.to_account_info())
+                        .collection(Some(&self.collection.to_account_info()))
+                        .payer(&self.user.to_account_info())
+                        .authority(Some(&self.collection_info.to_account_info()))
+                        .system_program(&self.system_program.to_account_info())
+                        .plugin_type(PluginType::Attributes)
+                        .new_authority(config_authority)
+                        .invoke_signed(signer_seeds)?;
+                }
+                update_attributes(
+                    &self.core_program,
+                    &self.asset,
+                    &self.collection,
+                    &self.config,
+                    &self.user,
+                    &self.system_program,
+                    attributes,
+                )?;
+            }
+        }
+
+        AddPluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: true }))
+            .init_authority(PluginAuthority::Address {
+                address: self.config.key(),
+            })
+            .invoke()?;
+
+        self.user_account.start_streak(now);
+        self.user_account.amount_staked = self
+            .user_account
+            .amount_staked
+            .checked_add(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// The asset's attribute list, and the authority of its `Attributes` plugin
+/// if it has one.
+pub fn asset_attributes(asset: &AccountInfo) -> (Vec<Attribute>, Option<PluginAuthority>) {
+    match fetch_plugin::<BaseAssetV1, Attributes>(asset, PluginType::Attributes) {
+        Ok((authority, attributes, _)) => (attributes.attribute_list, Some(authority)),
+        Err(_) => (vec![], None),
+    }
+}
+
+/// Numeric value of `key`, 0 if missing or not a number.
+pub fn attribute_value(attributes: &[Attribute], key: &str) -> i64 {
+    attributes
+        .iter()
+        .find(|attribute| attribute.key == key)
+        .and_then(|attribute| attribute.value.parse().ok())
+        .unwrap_or(0)
+}
+
+pub fn set_attribute(attributes: &mut Vec<Attribute>, key: &str, value: i64) {
+    match attributes.iter_mut().find(|attribute| attribute.key == key) {
+        Some(attribute) => attribute.value = value.to_string(),
+        None => attributes.push(Attribute {
+            key: key.to_string(),
+            value: value.to_string(),
+        }),
+    }
+}
+
+/// Replaces the asset's attribute list. `stake_on_asset` makes the config PDA
+/// the plugin's authority, so only the pool's staking logic can change it.
+pub fn update_attributes<'info>(
+    core_program: &AccountInfo<'info>,
+    asset: &AccountInfo<'info>,
+    collection: &AccountInfo<'info>,
+    config: &Account<'info, StakeConfig>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    attributes: Vec<Attribute>,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[b"config", config.collection.as_ref(), &[config.bump]]];
+
+    UpdatePluginV1CpiBuilder::new(core_program)
+        .asset(asset)
+        .collection(Some(collection))
+        .payer(payer)
+        .authority(Some(&config.to_account_info()))
+        .system_program(system_program)
+        .plugin(Plugin::Attributes(Attributes {
+            attribute_list: attributes,
+        }))
+        .invoke_signed(signer_seeds)?;
+
+    Ok(())
+}
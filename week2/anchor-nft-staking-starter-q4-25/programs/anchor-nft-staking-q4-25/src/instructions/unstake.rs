@@ -31,15 +31,16 @@ pub struct Unstake<'info> {
     pub asset: UncheckedAccount<'info>,
     #[account(
         mut,
-        seeds= [b"user", user.key().as_ref()],
-        bump = user_account.bump
-    )]
-    pub user_account: Account<'info, UserAccount>,
-    #[account(
-        seeds = [b"config".as_ref()],
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
         bump = config.bump
     )]
     pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds= [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
     #[account(
         mut,
         close = user,
@@ -56,17 +57,34 @@ pub struct Unstake<'info> {
 
 impl<'info> Unstake<'info> {
     pub fn unstake(&mut self) -> Result<()> {
-        let time_elapsed =
-            ((Clock::get()?.unix_timestamp - self.stake_account.staked_at) / 86400) as u32;
+        let now = Clock::get()?.unix_timestamp;
         require!(
-            time_elapsed >= self.config.freeze_period,
-            StakeError::FreezePeriodNotPassed
+            now >= self.stake_account.unlock_at,
+            StakeError::LockPeriodNotPassed
         );
 
+        self.user_account.update_streak(now);
+        let from = self.stake_account.last_claimed_at;
+        let (points, penalty_share) = self.stake_account.settle(now, &self.config)?;
+        self.user_account.accrue_streams(
+            &self.config,
+            points,
+            from,
+            self.stake_account.last_claimed_at,
+        )?;
+        let points = self.config.with_streak_bonus(
+            points
+                .checked_add(penalty_share)
+                .ok_or(ProgramError::ArithmeticOverflow)?,
+            self.user_account.streak_start,
+            from,
+            self.stake_account.last_claimed_at,
+        )?;
+        let forfeited = self.stake_account.unvested_penalty(&self.config);
         self.user_account.points = self
             .user_account
             .points
-            .checked_add(time_elapsed * self.config.points_per_stake as u32)
+            .checked_add(points)
             .ok_or(ProgramError::ArithmeticOverflow)?;
 
         let signers_seeds: &[&[&[u8]]] = &[&[
@@ -98,6 +116,13 @@ impl<'info> Unstake<'info> {
             .amount_staked
             .checked_sub(1)
             .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.user_account.end_streak_if_empty();
+        self.config.total_staked = self
+            .config
+            .total_staked
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.config.distribute_penalty(forfeited)?;
 
         Ok(())
     }
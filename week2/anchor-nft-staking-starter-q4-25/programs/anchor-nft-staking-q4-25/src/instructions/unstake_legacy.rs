@@ -0,0 +1,119 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    state::{
+        close_legacy, load_legacy, LegacyStakeAccount, LegacyStakeConfig, LegacyUserAccount,
+        StakeAccount, StakeConfig, UserAccount, SECONDS_PER_DAY,
+    },
+};
+
+#[derive(Accounts)]
+pub struct UnstakeLegacy<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: collection is verified by the core program
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: asset is verified by the core program
+    #[account(
+        mut,
+        constraint = asset.owner == &CORE_PROGRAM_ID @ StakeError::InvalidAsset,
+        constraint = !asset.data_is_empty() @ StakeError::AssetNotInitialized
+    )]
+    pub asset: UncheckedAccount<'info>,
+    /// CHECK: the legacy pool's config, read with `load_legacy`
+    #[account(seeds = [b"config".as_ref()], bump)]
+    pub legacy_config: UncheckedAccount<'info>,
+    /// CHECK: the user's account in the legacy pool, read with `load_legacy`
+    #[account(
+        mut,
+        seeds = [b"user", user.key().as_ref()],
+        bump
+    )]
+    pub legacy_user_account: UncheckedAccount<'info>,
+    /// CHECK: the asset's stake account in the legacy pool, read with
+    /// `load_legacy`
+    #[account(
+        mut,
+        seeds = [b"stake", legacy_config.key().as_ref(), asset.key().as_ref()],
+        bump
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+    /// CHECK: verified by the metaplex core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeLegacy<'info> {
+    /// Releases an asset staked before pools were keyed by collection. Its
+    /// FreezeDelegate authority is still the stake account under the legacy
+    /// `[b"config"]` pool, which no current pool can sign for. Points are
+    /// paid the legacy way, whole days times the legacy `points_per_stake`,
+    /// into the legacy user account for `migrate_user` to carry over.
+    pub fn unstake_legacy(&mut self) -> Result<()> {
+        let config: LegacyStakeConfig =
+            load_legacy::<StakeConfig, _>(&self.legacy_config, LegacyStakeConfig::LEN)?;
+        let stake: LegacyStakeAccount =
+            load_legacy::<StakeAccount, _>(&self.stake_account, LegacyStakeAccount::LEN)?;
+        let mut user_account: LegacyUserAccount =
+            load_legacy::<UserAccount, _>(&self.legacy_user_account, LegacyUserAccount::LEN)?;
+        require_keys_eq!(stake.owner, self.user.key(), StakeError::NotOwner);
+
+        let days = (Clock::get()?.unix_timestamp - stake.staked_at) / SECONDS_PER_DAY;
+        let days = u32::try_from(days).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        require!(
+            days >= config.freeze_period,
+            StakeError::LockPeriodNotPassed
+        );
+
+        user_account.points = days
+            .checked_mul(config.points_per_stake as u32)
+            .and_then(|points| user_account.points.checked_add(points))
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        user_account.amount_staked = user_account
+            .amount_staked
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        {
+            let mut data = self.legacy_user_account.try_borrow_mut_data()?;
+            user_account.serialize(&mut &mut data[UserAccount::DISCRIMINATOR.len()..])?;
+        }
+
+        let signers_seeds: &[&[&[u8]]] = &[&[
+            b"stake",
+            &self.legacy_config.key().to_bytes(),
+            &self.asset.key().to_bytes(),
+            &[stake.bump],
+        ]];
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.stake_account.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signers_seeds)?;
+
+        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .plugin_type(PluginType::FreezeDelegate)
+            .invoke()?;
+
+        close_legacy(&self.stake_account, &self.user)
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    state::{StakeAccount, StakeConfig, UserAccount},
+};
+
+// Each asset is passed in `remaining_accounts` as [asset, stake_account]
+pub const UNSTAKE_MANY_GROUP_LEN: usize = 2;
+
+#[derive(Accounts)]
+pub struct UnstakeMany<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: collection is verified by the core program
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds= [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    /// CHECK: verified by the metaplex core
+    #[account(address=CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeMany<'info> {
+    pub fn unstake_many(&mut self, remaining_accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !remaining_accounts.is_empty()
+                && remaining_accounts.len() % UNSTAKE_MANY_GROUP_LEN == 0,
+            StakeError::InvalidStakeAccount
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        // The streak is only ended after the loop, every asset gets its bonus
+        self.user_account.update_streak(now);
+        for group in remaining_accounts.chunks(UNSTAKE_MANY_GROUP_LEN) {
+            let (asset, stake_info) = (&group[0], &group[1]);
+            let mut stake_account = self.load_stake_account(asset, stake_info)?;
+            require!(
+                now >= stake_account.unlock_at,
+                StakeError::LockPeriodNotPassed
+            );
+
+            let from = stake_account.last_claimed_at;
+            let (points, penalty_share) = stake_account.settle(now, &self.config)?;
+            self.user_account.accrue_streams(
+                &self.config,
+                points,
+                from,
+                stake_account.last_claimed_at,
+            )?;
+            let points = self.config.with_streak_bonus(
+                points
+                    .checked_add(penalty_share)
+                    .ok_or(ProgramError::ArithmeticOverflow)?,
+                self.user_account.streak_start,
+                from,
+                stake_account.last_claimed_at,
+            )?;
+            let forfeited = stake_account.unvested_penalty(&self.config);
+            self.user_account.points = self
+                .user_account
+                .points
+                .checked_add(points)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+
+            let signers_seeds: &[&[&[u8]]] = &[&[
+                b"stake",
+                &self.config.key().to_bytes(),
+                &asset.key().to_bytes(),
+                &[stake_account.bump],
+            ]];
+            UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+                .asset(asset)
+                .collection(Some(&self.collection.to_account_info()))
+                .payer(&self.user.to_account_info())
+                .authority(Some(stake_info))
+                .system_program(&self.system_program.to_account_info())
+                .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+                .invoke_signed(signers_seeds)?;
+
+            RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+                .asset(asset)
+                .collection(Some(&self.collection.to_account_info()))
+                .payer(&self.user.to_account_info())
+                .authority(None)
+                .system_program(&self.system_program.to_account_info())
+                .plugin_type(PluginType::FreezeDelegate)
+                .invoke()?;
+
+            stake_account.close(self.user.to_account_info())?;
+
+            self.user_account.amount_staked = self
+                .user_account
+                .amount_staked
+                .checked_sub(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            self.config.total_staked = self
+                .config
+                .total_staked
+                .checked_sub(1)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            self.config.distribute_penalty(forfeited)?;
+        }
+        self.user_account.end_streak_if_empty();
+
+        Ok(())
+    }
+
+    // Same checks `Unstake` gets from its account constraints
+    fn load_stake_account(
+        &self,
+        asset: &AccountInfo<'info>,
+        stake_info: &'info AccountInfo<'info>,
+    ) -> Result<Account<'info, StakeAccount>> {
+        require!(stake_info.is_writable, StakeError::InvalidStakeAccount);
+        let stake_account = Account::<StakeAccount>::try_from(stake_info)?;
+
+        require_keys_eq!(stake_account.owner, self.user.key(), StakeError::NotOwner);
+        require_keys_eq!(stake_account.mint, asset.key(), StakeError::InvalidAsset);
+
+        let expected = Pubkey::create_program_address(
+            &[
+                b"stake",
+                self.config.key().as_ref(),
+                asset.key().as_ref(),
+                &[stake_account.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| error!(StakeError::InvalidStakeAccount))?;
+        require_keys_eq!(expected, stake_info.key(), StakeError::InvalidStakeAccount);
+
+        Ok(stake_account)
+    }
+}
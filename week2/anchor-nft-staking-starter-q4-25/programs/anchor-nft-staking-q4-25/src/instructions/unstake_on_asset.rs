@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+use mpl_core::{
+    instructions::{RemovePluginV1CpiBuilder, UpdatePluginV1CpiBuilder},
+    types::{FreezeDelegate, Plugin, PluginType},
+    ID as CORE_PROGRAM_ID,
+};
+
+use crate::{
+    errors::StakeError,
+    instructions::{
+        asset_attributes, attribute_value, set_attribute, trait_boost, update_attributes,
+        verify_asset, POINTS_INDEX_KEY, STAKED_AT_KEY, STAKED_TIME_KEY,
+    },
+    state::{BoostTable, StakeConfig, UserAccount, BPS_DENOMINATOR, SECONDS_PER_DAY},
+};
+
+#[derive(Accounts)]
+pub struct UnstakeOnAsset<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    /// CHECK: collection is verified by the core program
+    #[account(
+        mut,
+        constraint = collection.owner == &CORE_PROGRAM_ID @ StakeError::InvalidCollection,
+        constraint = !collection.data_is_empty() @ StakeError::CollectionNotInitialized
+    )]
+    pub collection: UncheckedAccount<'info>,
+    /// CHECK: asset is verified by the core program
+    #[account(
+        mut,
+        constraint = asset.owner == &CORE_PROGRAM_ID @ StakeError::InvalidAsset,
+        constraint = !asset.data_is_empty() @ StakeError::AssetNotInitialized
+    )]
+    pub asset: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config".as_ref(), collection.key().as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+    #[account(
+        mut,
+        seeds = [b"user", config.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Account<'info, UserAccount>,
+    #[account(
+        seeds = [b"boosts".as_ref(), config.key().as_ref()],
+        bump = boost_table.bump
+    )]
+    pub boost_table: Option<Account<'info, BoostTable>>,
+    /// CHECK: verified by the metaplex core
+    #[account(address = CORE_PROGRAM_ID)]
+    pub core_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> UnstakeOnAsset<'info> {
+    /// Adds the time since `staked_at` to the asset's `staked_time`, credits
+    /// the points `points_index` grew by since then and thaws the asset. The trait boost is read from
+    /// the pool's current table.
+    pub fn unstake_on_asset(&mut self) -> Result<()> {
+        verify_asset(&self.asset, &self.collection.key(), &self.user.key())?;
+
+        let (mut attributes, _) = asset_attributes(&self.asset);
+        let staked_at = attribute_value(&attributes, STAKED_AT_KEY);
+        require!(staked_at > 0, StakeError::AssetNotStaked);
+
+        let now = Clock::get()?.unix_timestamp;
+        let elapsed = now.saturating_sub(staked_at).max(0);
+        let staked_time = attribute_value(&attributes, STAKED_TIME_KEY).saturating_add(elapsed);
+        let accrued = self
+            .config
+            .points_index_at(now)
+            .saturating_sub(attribute_value(&attributes, POINTS_INDEX_KEY).max(0) as u128);
+        set_attribute(&mut attributes, STAKED_AT_KEY, 0);
+        set_attribute(&mut attributes, STAKED_TIME_KEY, staked_time);
+        set_attribute(&mut attributes, POINTS_INDEX_KEY, 0);
+
+        let boost_bps = trait_boost(&self.asset, self.boost_table.as_deref());
+        let points =
+            accrued * boost_bps as u128 / (SECONDS_PER_DAY as u128 * BPS_DENOMINATOR as u128);
+        let points = u64::try_from(points).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        self.user_account.update_streak(now);
+        let points = self.config.with_streak_bonus(
+            points,
+            self.user_account.streak_start,
+            staked_at,
+            now,
+        )?;
+        self.user_account.points = self
+            .user_account
+            .points
+            .checked_add(points)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        update_attributes(
+            &self.core_program,
+            &self.asset,
+            &self.collection,
+            &self.config,
+            &self.user,
+            &self.system_program,
+            attributes,
+        )?;
+
+        let signers_seeds: &[&[&[u8]]] = &[&[
+            b"config",
+            self.config.collection.as_ref(),
+            &[self.config.bump],
+        ]];
+        UpdatePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(Some(&self.config.to_account_info()))
+            .system_program(&self.system_program.to_account_info())
+            .plugin(Plugin::FreezeDelegate(FreezeDelegate { frozen: false }))
+            .invoke_signed(signers_seeds)?;
+
+        RemovePluginV1CpiBuilder::new(&self.core_program.to_account_info())
+            .asset(&self.asset.to_account_info())
+            .collection(Some(&self.collection.to_account_info()))
+            .payer(&self.user.to_account_info())
+            .authority(None)
+            .system_program(&self.system_program.to_account_info())
+            .plugin_type(PluginType::FreezeDelegate)
+            .invoke()?;
+
+        self.user_account.amount_staked = self
+            .user_account
+            .amount_staked
+            .checked_sub(1)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        self.user_account.end_streak_if_empty();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,75 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    errors::StakeError,
+    state::{LockTier, PenaltyMode, StakeConfig, StreakTier, BPS_DENOMINATOR},
+};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = admin @ StakeError::NotAuthorized,
+        seeds = [b"config".as_ref(), config.collection.as_ref()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, StakeConfig>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    /// A new `points_per_stake` applies from now on, what staked assets
+    /// accrued at the old rate is checkpointed into `points_index` first. Lock
+    /// tier changes only affect new stakes, existing ones keep the multiplier
+    /// copied into their `StakeAccount`.
+    pub fn update_config(
+        &mut self,
+        points_per_stake: u8,
+        reward_rate_bps: u64,
+        max_stake: u8,
+        lock_tiers: Vec<LockTier>,
+    ) -> Result<()> {
+        StakeConfig::validate_lock_tiers(&lock_tiers)?;
+        require!(reward_rate_bps > 0, StakeError::InvalidRewardRate);
+
+        self.config.checkpoint_points(Clock::get()?.unix_timestamp);
+        self.config.points_per_stake = points_per_stake;
+        self.config.reward_rate_bps = reward_rate_bps;
+        self.config.max_stake = max_stake;
+        self.config.lock_tiers = lock_tiers;
+
+        Ok(())
+    }
+
+    pub fn transfer_admin(&mut self, new_admin: Pubkey) -> Result<()> {
+        self.config.pending_admin = Some(new_admin);
+
+        Ok(())
+    }
+
+    pub fn set_unstake_penalty(&mut self, penalty_bps: u16, mode: PenaltyMode) -> Result<()> {
+        require!(
+            penalty_bps as u64 <= BPS_DENOMINATOR,
+            StakeError::InvalidUnstakePenalty
+        );
+
+        self.config.early_unstake_penalty_bps = penalty_bps;
+        self.config.penalty_mode = mode;
+
+        Ok(())
+    }
+
+    pub fn set_streak_tiers(&mut self, streak_tiers: Vec<StreakTier>) -> Result<()> {
+        StakeConfig::validate_streak_tiers(&streak_tiers)?;
+        self.config.streak_tiers = streak_tiers;
+
+        Ok(())
+    }
+
+    pub fn pause(&mut self, paused: bool) -> Result<()> {
+        self.config.paused = paused;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use crate::{errors::StakeError, state::CollectionInfo};
+
+#[derive(Accounts)]
+pub struct WithdrawProceeds<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(
+        has_one = authority @ StakeError::NotAuthorized,
+        seeds = [b"collection_info", collection_info.collection.as_ref()],
+        bump = collection_info.bump
+    )]
+    pub collection_info: Account<'info, CollectionInfo>,
+
+    #[account(
+        mut,
+        seeds = [b"treasury", collection_info.collection.as_ref()],
+        bump = collection_info.treasury_bump
+    )]
+    pub treasury: SystemAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawProceeds<'info> {
+    /// Sends everything the treasury holds above its rent exempt minimum to
+    /// the collection authority.
+    pub fn withdraw_proceeds(&mut self) -> Result<()> {
+        let amount = self
+            .treasury
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(0));
+        require!(amount > 0, StakeError::NoProceeds);
+
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"treasury",
+            self.collection_info.collection.as_ref(),
+            &[self.collection_info.treasury_bump],
+        ]];
+
+        transfer(
+            CpiContext::new_with_signer(
+                self.system_program.to_account_info(),
+                Transfer {
+                    from: self.treasury.to_account_info(),
+                    to: self.authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )
+    }
+}
@@ -5,6 +5,7 @@ mod instructions;
 mod state;
 
 use instructions::*;
+use state::{LockTier, PenaltyMode, RewardMode, StreakTier, TraitBoost};
 
 declare_id!("6VUmb8CTjfx5B8ViBwUsTJuKh2y1kZVs5LViciek2eAB");
 
@@ -15,11 +16,19 @@ pub mod anchor_nft_staking_q4_25 {
     pub fn initialize_config(
         ctx: Context<InitializeConfig>,
         points_per_stake: u8,
+        reward_rate_bps: u64,
         max_stake: u8,
-        freeze_period: u32,
+        lock_tiers: Vec<LockTier>,
+        emissions: EmissionArgs,
     ) -> Result<()> {
-        ctx.accounts
-            .initialize_config(points_per_stake, max_stake, freeze_period, &ctx.bumps)
+        ctx.accounts.initialize_config(
+            points_per_stake,
+            reward_rate_bps,
+            max_stake,
+            lock_tiers,
+            emissions,
+            &ctx.bumps,
+        )
     }
 
     pub fn initialize_user(ctx: Context<Initialize>) -> Result<()> {
@@ -37,15 +46,134 @@ pub mod anchor_nft_staking_q4_25 {
         ctx.accounts.mint_nft()
     }
 
-    pub fn stake(ctx: Context<Stake>) -> Result<()> {
-        ctx.accounts.stake(&ctx.bumps)
+    pub fn withdraw_proceeds(ctx: Context<WithdrawProceeds>) -> Result<()> {
+        ctx.accounts.withdraw_proceeds()
+    }
+
+    pub fn stake(ctx: Context<Stake>, tier: u8) -> Result<()> {
+        ctx.accounts.stake(tier, &ctx.bumps)
     }
 
     pub fn unstake(ctx: Context<Unstake>) -> Result<()> {
         ctx.accounts.unstake()
     }
 
-    pub fn claim(ctx: Context<Claim>) -> Result<()> {
-        ctx.accounts.claim()
+    pub fn unstake_legacy(ctx: Context<UnstakeLegacy>) -> Result<()> {
+        ctx.accounts.unstake_legacy()
+    }
+
+    pub fn emergency_unstake(ctx: Context<EmergencyUnstake>) -> Result<()> {
+        ctx.accounts.emergency_unstake()
+    }
+
+    pub fn stake_on_asset(ctx: Context<StakeOnAsset>) -> Result<()> {
+        ctx.accounts.stake_on_asset()
+    }
+
+    pub fn unstake_on_asset(ctx: Context<UnstakeOnAsset>) -> Result<()> {
+        ctx.accounts.unstake_on_asset()
+    }
+
+    pub fn claim<'info>(ctx: Context<'_, '_, 'info, 'info, Claim<'info>>) -> Result<()> {
+        ctx.accounts.claim(ctx.remaining_accounts)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        points_per_stake: u8,
+        reward_rate_bps: u64,
+        max_stake: u8,
+        lock_tiers: Vec<LockTier>,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_config(points_per_stake, reward_rate_bps, max_stake, lock_tiers)
+    }
+
+    pub fn transfer_admin(ctx: Context<UpdateConfig>, new_admin: Pubkey) -> Result<()> {
+        ctx.accounts.transfer_admin(new_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn set_unstake_penalty(
+        ctx: Context<UpdateConfig>,
+        penalty_bps: u16,
+        mode: PenaltyMode,
+    ) -> Result<()> {
+        ctx.accounts.set_unstake_penalty(penalty_bps, mode)
+    }
+
+    pub fn set_streak_tiers(
+        ctx: Context<UpdateConfig>,
+        streak_tiers: Vec<StreakTier>,
+    ) -> Result<()> {
+        ctx.accounts.set_streak_tiers(streak_tiers)
+    }
+
+    pub fn pause(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        ctx.accounts.pause(paused)
+    }
+
+    pub fn set_trait_boosts(ctx: Context<SetTraitBoosts>, boosts: Vec<TraitBoost>) -> Result<()> {
+        ctx.accounts.set_trait_boosts(boosts, &ctx.bumps)
+    }
+
+    pub fn stake_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, StakeMany<'info>>,
+        tier: u8,
+    ) -> Result<()> {
+        ctx.accounts.stake_many(ctx.remaining_accounts, tier)
+    }
+
+    pub fn unstake_many<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UnstakeMany<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.unstake_many(ctx.remaining_accounts)
+    }
+
+    pub fn migrate_user(ctx: Context<MigrateUser>) -> Result<()> {
+        ctx.accounts.migrate_user(&ctx.bumps)
+    }
+
+    pub fn initialize_treasury_config(
+        ctx: Context<InitializeTreasuryConfig>,
+        points_per_stake: u8,
+        reward_rate_bps: u64,
+        max_stake: u8,
+        lock_tiers: Vec<LockTier>,
+        emissions: EmissionArgs,
+    ) -> Result<()> {
+        ctx.accounts.initialize_treasury_config(
+            points_per_stake,
+            reward_rate_bps,
+            max_stake,
+            lock_tiers,
+            emissions,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn fund_rewards(ctx: Context<FundRewards>, amount: u64) -> Result<()> {
+        ctx.accounts.fund_rewards(amount)
+    }
+
+    pub fn add_reward_stream(
+        ctx: Context<AddRewardStream>,
+        mode: RewardMode,
+        reward_per_point: u64,
+        start: i64,
+        end: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .add_reward_stream(mode, reward_per_point, start, end)
+    }
+
+    pub fn release_mint_authority(
+        ctx: Context<ReleaseMintAuthority>,
+        new_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.release_mint_authority(new_authority)
     }
 }
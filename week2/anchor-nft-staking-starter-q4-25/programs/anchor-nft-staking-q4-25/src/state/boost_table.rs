@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use mpl_core::types::Attribute;
+
+use crate::{errors::StakeError, state::BPS_DENOMINATOR};
+
+pub const MAX_TRAIT_BOOSTS: usize = 16;
+pub const MAX_TRAIT_KEY_LEN: usize = 32;
+pub const MAX_TRAIT_VALUE_LEN: usize = 64;
+
+// Per-pool trait multipliers, at [b"boosts", config]
+#[account]
+#[derive(InitSpace)]
+pub struct BoostTable {
+    pub config: Pubkey,
+    #[max_len(MAX_TRAIT_BOOSTS)]
+    pub boosts: Vec<TraitBoost>,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct TraitBoost {
+    #[max_len(MAX_TRAIT_KEY_LEN)]
+    pub key: String,
+    #[max_len(MAX_TRAIT_VALUE_LEN)]
+    pub value: String,
+    // 10_000 = 1x
+    pub multiplier_bps: u16,
+}
+
+impl BoostTable {
+    /// Boosts can't go below 1x. The table is optional when staking, so a
+    /// penalty could be dodged by leaving it out.
+    pub fn validate(boosts: &[TraitBoost]) -> Result<()> {
+        require!(
+            boosts.len() <= MAX_TRAIT_BOOSTS
+                && boosts.iter().all(|boost| {
+                    boost.key.len() <= MAX_TRAIT_KEY_LEN
+                        && boost.value.len() <= MAX_TRAIT_VALUE_LEN
+                        && boost.multiplier_bps as u64 >= BPS_DENOMINATOR
+                }),
+            StakeError::InvalidTraitBoosts
+        );
+        Ok(())
+    }
+
+    /// Highest multiplier among the asset's traits, or `None` if none of
+    /// them is in the table.
+    pub fn boost_for(&self, attributes: &[Attribute]) -> Option<u16> {
+        attributes
+            .iter()
+            .flat_map(|attribute| {
+                self.boosts.iter().filter(move |boost| {
+                    boost.key == attribute.key && boost.value == attribute.value
+                })
+            })
+            .map(|boost| boost.multiplier_bps)
+            .max()
+    }
+}
@@ -13,5 +13,11 @@ pub struct CollectionInfo {
     pub nft_name: String,
     #[max_len(200)]
     pub nft_uri: String,
+    pub max_supply: u32,
+    pub minted: u32,
+    pub price_lamports: u64,
+    // System owned PDA at [b"treasury", collection] collecting mint payments
+    pub treasury: Pubkey,
+    pub treasury_bump: u8,
     pub bump: u8,
 }
@@ -0,0 +1,79 @@
+use anchor_lang::{prelude::*, system_program};
+
+use crate::errors::StakeError;
+
+// Layouts of the single pool from before pools were keyed by collection.
+// They share their discriminators with the current accounts, so they're
+// only read through `load_legacy`, never as `Account`s
+
+// At [b"config"]
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyStakeConfig {
+    pub points_per_stake: u8,
+    pub max_stake: u8,
+    pub freeze_period: u32,
+    pub rewards_bump: u8,
+    pub bump: u8,
+}
+
+impl LegacyStakeConfig {
+    pub const LEN: usize = 1 + 1 + 4 + 1 + 1;
+}
+
+// At [b"user", user]
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyUserAccount {
+    pub points: u32,
+    pub amount_staked: u8,
+    pub bump: u8,
+}
+
+impl LegacyUserAccount {
+    pub const LEN: usize = 4 + 1 + 1;
+}
+
+// At [b"stake", legacy config, asset], the FreezeDelegate authority of
+// assets staked in the legacy pool
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyStakeAccount {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub staked_at: i64,
+    pub bump: u8,
+}
+
+impl LegacyStakeAccount {
+    pub const LEN: usize = 32 + 32 + 8 + 1;
+}
+
+/// Reads a legacy account of `len` bytes that was written with the
+/// discriminator of `D`, its current counterpart.
+pub fn load_legacy<D: Discriminator, T: AnchorDeserialize>(
+    info: &AccountInfo,
+    len: usize,
+) -> Result<T> {
+    require_keys_eq!(*info.owner, crate::ID, StakeError::NotOwner);
+    let data = info.try_borrow_data()?;
+    require!(
+        data.starts_with(D::DISCRIMINATOR),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    let mut legacy = &data[D::DISCRIMINATOR.len()..];
+    require_eq!(legacy.len(), len, ErrorCode::AccountDidNotDeserialize);
+
+    Ok(T::deserialize(&mut legacy)?)
+}
+
+/// Closes a legacy account into `destination`. There's no `Account` to close
+/// it through.
+pub fn close_legacy(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(info.lamports())
+        .ok_or(ProgramError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+    info.assign(&system_program::ID);
+    info.resize(0)?;
+
+    Ok(())
+}
@@ -9,3 +9,9 @@ pub use stake_account::*;
 
 pub mod collection_info;
 pub use collection_info::*;
+
+pub mod boost_table;
+pub use boost_table::*;
+
+pub mod legacy;
+pub use legacy::*;
@@ -1,10 +1,82 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{StakeConfig, BPS_DENOMINATOR, PENALTY_INDEX_PRECISION};
+
+pub const SECONDS_PER_DAY: i64 = 86_400;
+// Redistributed penalty points vest over a stake's first week, so an asset
+// staked just before someone else's early unstake can't take its share and leave
+pub const PENALTY_VESTING_PERIOD: i64 = 7 * SECONDS_PER_DAY;
+// Points accrue at `rate() / POINTS_PERIOD` per unit of `StakeConfig::points_index`
+const POINTS_PERIOD: u128 = SECONDS_PER_DAY as u128 * (BPS_DENOMINATOR as u128).pow(2);
+
 #[account]
 #[derive(InitSpace)]
 pub struct StakeAccount {
     pub owner: Pubkey,
     pub mint: Pubkey,
     pub staked_at: i64,
+    pub last_claimed_at: i64,
+    // Lock tier chosen at stake time. Its multiplier and the trait boost are
+    // copied so later config updates don't change the terms of existing stakes
+    pub tier: u8,
+    pub multiplier_bps: u16,
+    pub boost_bps: u16,
+    pub unlock_at: i64,
+    // `StakeConfig::penalty_index` as of the last settle
+    pub penalty_index: u128,
+    // `StakeConfig::points_index` as of the last settle, less what the
+    // fraction of a point carried to the next settle hasn't used up
+    pub points_index: u128,
     pub bump: u8,
 }
+
+impl StakeAccount {
+    /// Settles the points accrued since the last settle, at the pool's
+    /// `points_per_stake` per day as it stood over that time, scaled by the
+    /// tier multiplier and trait boost, pro rata per second. Returns them
+    /// along with the vested part of this asset's share of any penalties
+    /// redistributed since the last settle. Only the part of the index that
+    /// produced whole points is consumed, so the remainder keeps accruing
+    /// towards the next claim.
+    pub fn settle(&mut self, now: i64, config: &StakeConfig) -> Result<(u64, u64)> {
+        // The unvested part stays pending in the index until a later settle.
+        // Like the points below, only whole points are consumed from it
+        let vested = now
+            .saturating_sub(self.staked_at)
+            .clamp(0, PENALTY_VESTING_PERIOD) as u128;
+        let penalty_share = (config.penalty_index - self.penalty_index)
+            .checked_mul(vested)
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / PENALTY_VESTING_PERIOD as u128
+            / PENALTY_INDEX_PRECISION;
+        self.penalty_index += penalty_share * PENALTY_INDEX_PRECISION;
+
+        let accrued = config.points_index_at(now) - self.points_index;
+        let rate = self.rate();
+        let points = if rate == 0 {
+            self.points_index += accrued;
+            0
+        } else {
+            let points = accrued * rate / POINTS_PERIOD;
+            self.points_index += points * POINTS_PERIOD / rate;
+            points
+        };
+        self.last_claimed_at = now;
+
+        let points = u64::try_from(points).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        let penalty_share =
+            u64::try_from(penalty_share).map_err(|_| ProgramError::ArithmeticOverflow)?;
+        Ok((points, penalty_share))
+    }
+
+    /// Whole penalty points still vesting after a settle, forfeited if the
+    /// stake is closed.
+    pub fn unvested_penalty(&self, config: &StakeConfig) -> u64 {
+        u64::try_from((config.penalty_index - self.penalty_index) / PENALTY_INDEX_PRECISION)
+            .unwrap_or(u64::MAX)
+    }
+
+    fn rate(&self) -> u128 {
+        self.multiplier_bps as u128 * self.boost_bps as u128
+    }
+}
@@ -1,11 +1,260 @@
 use anchor_lang::prelude::*;
 
+use crate::{errors::StakeError, state::SECONDS_PER_DAY};
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const MAX_LOCK_TIERS: usize = 4;
+pub const MAX_REWARD_STREAMS: usize = 4;
+pub const MAX_STREAK_TIERS: usize = 4;
+// Fixed point scale of `StakeConfig::penalty_index`
+pub const PENALTY_INDEX_PRECISION: u128 = 1_000_000_000_000;
+
+// One staking pool per collection, at [b"config", collection]
 #[account]
 #[derive(InitSpace)]
 pub struct StakeConfig {
+    pub collection: Pubkey,
+    pub admin: Pubkey,
+    // Set by `transfer_admin`, becomes admin once it calls `accept_admin`
+    pub pending_admin: Option<Pubkey>,
+    // Blocks new stakes only, unstaking and claiming keep working
+    pub paused: bool,
     pub points_per_stake: u8,
+    // Reward tokens minted per point, 10_000 = one whole token. Scaled by the
+    // reward mint's decimals at claim time
+    pub reward_rate_bps: u64,
+    pub reward_mode: RewardMode,
+    // The [b"rewards", config] PDA in `Mint` mode, an existing token in `Treasury` mode
+    pub reward_mint: Pubkey,
+    pub emissions: EmissionSchedule,
     pub max_stake: u8,
-    pub freeze_period: u32,
+    // Assets currently staked through a `StakeAccount` across all users of
+    // the pool. On-asset stakes aren't counted, they don't take a share of
+    // redistributed penalties
+    pub total_staked: u32,
+    // Share of settled points `emergency_unstake` takes, and what happens to it
+    pub early_unstake_penalty_bps: u16,
+    pub penalty_mode: PenaltyMode,
+    // Redistributed penalty points per staked asset, scaled by
+    // PENALTY_INDEX_PRECISION. Only ever grows
+    pub penalty_index: u128,
+    // `points_per_stake` times the seconds it was in force, summed up to
+    // `points_index_updated_at`. Stakes earn on the growth of this since
+    // their last settle, so a new `points_per_stake` only counts from when
+    // it's set
+    pub points_index: u128,
+    pub points_index_updated_at: i64,
+    #[max_len(MAX_LOCK_TIERS)]
+    pub lock_tiers: Vec<LockTier>,
+    // Extra reward tokens paid on top of `reward_mint`. Append only, so the
+    // indexes in `UserAccount::stream_rewards` stay valid
+    #[max_len(MAX_REWARD_STREAMS)]
+    pub streams: Vec<RewardStream>,
+    // Multipliers for users who kept something staked for `min_days` in a
+    // row, sorted by `min_days`. Empty means no streak bonus
+    #[max_len(MAX_STREAK_TIERS)]
+    pub streak_tiers: Vec<StreakTier>,
     pub rewards_bump: u8,
     pub bump: u8,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RewardMode {
+    // `claim` mints, the config PDA is the mint authority
+    Mint,
+    // `claim` transfers from the config's ATA, topped up with `fund_rewards`
+    Treasury,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct StreakTier {
+    pub min_days: u16,
+    // 10_000 = 1x
+    pub multiplier_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct RewardStream {
+    pub mint: Pubkey,
+    // `Mint` needs the config PDA as mint authority, `Treasury` pays from
+    // the config's ATA for `mint`
+    pub mode: RewardMode,
+    // Base units of `mint` per point earned between `start` and `end`
+    pub reward_per_point: u64,
+    pub start: i64,
+    pub end: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum PenaltyMode {
+    Burn,
+    // Split evenly across the assets still staked in the pool, vesting over
+    // each stake's first `PENALTY_VESTING_PERIOD`
+    Redistribute,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LockTier {
+    pub lock_days: u16,
+    // 10_000 = 1x
+    pub multiplier_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct EmissionSchedule {
+    // Cap on everything `claim` ever pays out, in reward mint base units
+    pub max_supply: u64,
+    pub minted: u64,
+    pub start: i64,
+    // `reward_rate_bps` halves at the end of every epoch
+    pub epoch_duration: i64,
+}
+
+impl EmissionSchedule {
+    pub fn validate(max_supply: u64, epoch_duration: i64) -> Result<()> {
+        require!(
+            max_supply > 0 && epoch_duration > 0,
+            StakeError::InvalidEmissionSchedule
+        );
+        Ok(())
+    }
+
+    pub fn epoch(&self, now: i64) -> u64 {
+        (now.saturating_sub(self.start).max(0) / self.epoch_duration) as u64
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.max_supply.saturating_sub(self.minted)
+    }
+}
+
+impl StakeConfig {
+    pub fn validate_lock_tiers(lock_tiers: &[LockTier]) -> Result<()> {
+        require!(
+            !lock_tiers.is_empty()
+                && lock_tiers.len() <= MAX_LOCK_TIERS
+                && lock_tiers.iter().all(|tier| tier.multiplier_bps > 0),
+            StakeError::InvalidLockTiers
+        );
+        Ok(())
+    }
+
+    pub fn validate_streak_tiers(streak_tiers: &[StreakTier]) -> Result<()> {
+        require!(
+            streak_tiers.len() <= MAX_STREAK_TIERS
+                && streak_tiers.iter().all(|tier| tier.multiplier_bps > 0)
+                && streak_tiers
+                    .windows(2)
+                    .all(|pair| pair[0].min_days < pair[1].min_days),
+            StakeError::InvalidStreakTiers
+        );
+        Ok(())
+    }
+
+    /// `points` earned between `from` and `to` scaled by the streak tiers,
+    /// each stretch of that window at the tier the streak had reached by
+    /// then. A streak started at `streak_start` (0 for none) reaches a tier
+    /// `min_days` days later.
+    pub fn with_streak_bonus(
+        &self,
+        points: u64,
+        streak_start: i64,
+        from: i64,
+        to: i64,
+    ) -> Result<u64> {
+        if streak_start == 0 || self.streak_tiers.is_empty() {
+            return Ok(points);
+        }
+        let tier_start =
+            |tier: &StreakTier| streak_start.saturating_add(tier.min_days as i64 * SECONDS_PER_DAY);
+
+        let (weighted_bps, window) = if to <= from {
+            // Nothing accrued over time, e.g. only a penalty share
+            let multiplier_bps = self
+                .streak_tiers
+                .iter()
+                .rev()
+                .find(|tier| tier_start(tier) <= to)
+                .map_or(BPS_DENOMINATOR, |tier| tier.multiplier_bps as u64);
+            (multiplier_bps as u128, 1)
+        } else {
+            // Tiers are sorted, so each one takes over where the last ended
+            let mut weighted_bps = 0u128;
+            let mut cursor = from;
+            let mut multiplier_bps = BPS_DENOMINATOR;
+            for tier in &self.streak_tiers {
+                let start = tier_start(tier).clamp(from, to);
+                weighted_bps += (start - cursor) as u128 * multiplier_bps as u128;
+                cursor = start;
+                multiplier_bps = tier.multiplier_bps as u64;
+            }
+            weighted_bps += (to - cursor) as u128 * multiplier_bps as u128;
+            (weighted_bps, (to - from) as u128)
+        };
+
+        let points = (points as u128)
+            .checked_mul(weighted_bps)
+            .map(|points| points / (window * BPS_DENOMINATOR as u128))
+            .and_then(|points| u64::try_from(points).ok())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(points)
+    }
+
+    /// `points_index` brought forward to `now` at the current `points_per_stake`.
+    pub fn points_index_at(&self, now: i64) -> u128 {
+        let elapsed = now.saturating_sub(self.points_index_updated_at).max(0) as u128;
+        self.points_index + elapsed * self.points_per_stake as u128
+    }
+
+    /// Records what has accrued at the current rate, call before changing
+    /// `points_per_stake`.
+    pub fn checkpoint_points(&mut self, now: i64) {
+        self.points_index = self.points_index_at(now);
+        self.points_index_updated_at = now;
+    }
+
+    /// Applies a forfeited `penalty` once the unstaked asset has left
+    /// `total_staked`. Burned if the pool is set to burn or nobody is left to
+    /// receive it.
+    pub fn distribute_penalty(&mut self, penalty: u64) -> Result<()> {
+        if self.penalty_mode == PenaltyMode::Burn || self.total_staked == 0 {
+            return Ok(());
+        }
+
+        self.penalty_index = self
+            .penalty_index
+            .checked_add(penalty as u128 * PENALTY_INDEX_PRECISION / self.total_staked as u128)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+
+        Ok(())
+    }
+
+    /// Reward rate for the epoch `now` falls in, halved once per elapsed epoch.
+    pub fn current_rate_bps(&self, now: i64) -> u64 {
+        u32::try_from(self.emissions.epoch(now))
+            .ok()
+            .and_then(|epoch| self.reward_rate_bps.checked_shr(epoch))
+            .unwrap_or(0)
+    }
+
+    /// Whether `claim` has nothing left to pay on the main reward: the supply
+    /// cap is minted, or the rate has halved down to zero.
+    pub fn emissions_finished(&self, now: i64) -> bool {
+        self.emissions.remaining() == 0 || self.current_rate_bps(now) == 0
+    }
+
+    /// Base units of the reward mint owed for `points` at the current rate,
+    /// clamped to what is left under the supply cap.
+    pub fn reward_amount(&self, points: u64, decimals: u8, now: i64) -> Result<u64> {
+        let amount = 10u128
+            .checked_pow(decimals as u32)
+            .and_then(|scale| (points as u128).checked_mul(scale))
+            .and_then(|amount| amount.checked_mul(self.current_rate_bps(now) as u128))
+            .ok_or(ProgramError::ArithmeticOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let amount = u64::try_from(amount).unwrap_or(u64::MAX);
+
+        Ok(amount.min(self.emissions.remaining()))
+    }
+}
@@ -1,9 +1,78 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{StakeConfig, MAX_REWARD_STREAMS, SECONDS_PER_DAY};
+
+// A user's position in one pool, at [b"user", config, user]
 #[account]
 #[derive(InitSpace)]
 pub struct UserAccount {
-    pub points: u32,
+    pub config: Pubkey,
+    pub points: u64,
     pub amount_staked: u8,
+    // When the user last went from nothing staked to something staked, 0
+    // while nothing is. `streak_days` is refreshed from it on every settle
+    pub streak_start: i64,
+    pub streak_days: u16,
     pub bump: u8,
+    // Unclaimed base units per `StakeConfig::streams` entry, same order
+    #[max_len(MAX_REWARD_STREAMS)]
+    pub stream_rewards: Vec<u64>,
+}
+
+impl UserAccount {
+    /// Call before counting a new stake in `amount_staked`.
+    pub fn start_streak(&mut self, now: i64) {
+        if self.amount_staked == 0 {
+            self.streak_start = now;
+            self.streak_days = 0;
+        }
+    }
+
+    /// Call after an unstake, a streak ends once nothing is staked.
+    pub fn end_streak_if_empty(&mut self) {
+        if self.amount_staked == 0 {
+            self.streak_start = 0;
+            self.streak_days = 0;
+        }
+    }
+
+    pub fn update_streak(&mut self, now: i64) {
+        self.streak_days = if self.streak_start == 0 {
+            0
+        } else {
+            u16::try_from(now.saturating_sub(self.streak_start).max(0) / SECONDS_PER_DAY)
+                .unwrap_or(u16::MAX)
+        };
+    }
+
+    /// Credits every stream with its share of the `points` a stake earned
+    /// between `from` and `to`, pro rata to how much of that window the
+    /// stream was live.
+    pub fn accrue_streams(
+        &mut self,
+        config: &StakeConfig,
+        points: u64,
+        from: i64,
+        to: i64,
+    ) -> Result<()> {
+        self.stream_rewards.resize(config.streams.len(), 0);
+        let window = to.saturating_sub(from);
+        if window <= 0 {
+            return Ok(());
+        }
+
+        for (stream, pending) in config.streams.iter().zip(self.stream_rewards.iter_mut()) {
+            let live = to
+                .min(stream.end)
+                .saturating_sub(from.max(stream.start))
+                .max(0);
+            let points = (points as u128 * live as u128 / window as u128) as u64;
+            *pending = points
+                .checked_mul(stream.reward_per_point)
+                .and_then(|reward| pending.checked_add(reward))
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+        }
+
+        Ok(())
+    }
 }
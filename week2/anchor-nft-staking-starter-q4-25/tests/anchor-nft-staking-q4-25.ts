@@ -2,8 +2,18 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { AnchorNftStakingQ425 } from "../target/types/anchor_nft_staking_q4_25";
 import { PublicKey, Keypair, SystemProgram } from "@solana/web3.js";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
-import { MPL_CORE_PROGRAM_ID } from "@metaplex-foundation/mpl-core";
+import {
+  createMint,
+  getAssociatedTokenAddressSync,
+  getMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import {
+  MPL_CORE_PROGRAM_ID,
+  deserializeAssetV1,
+} from "@metaplex-foundation/mpl-core";
+import { lamports, publicKey as umiPublicKey } from "@metaplex-foundation/umi";
 import { assert } from "chai";
 
 describe("anchor-nft-staking-q4-25", () => {
@@ -19,11 +29,27 @@ describe("anchor-nft-staking-q4-25", () => {
   const user = Keypair.generate();
   const collection = Keypair.generate();
   const asset = Keypair.generate();
+  // Staked in on-asset mode, without a stake account
+  const onAsset = Keypair.generate();
 
   // Config parameters
   const pointsPerStake = 10;
+  // One whole reward token per point
+  const rewardRateBps = new anchor.BN(10_000);
+  // 1M whole tokens at 6 decimals, rate halving every 30 days
+  const emissions = {
+    maxSupply: new anchor.BN(1_000_000_000_000),
+    epochDuration: new anchor.BN(30 * 86_400),
+  };
   const maxStake = 5;
-  const freezePeriod = 0; // 0 days for testing
+  // Tier 0 has no lock for testing
+  const lockTiers = [
+    { lockDays: 0, multiplierBps: 10_000 },
+    { lockDays: 7, multiplierBps: 11_000 },
+    { lockDays: 30, multiplierBps: 12_500 },
+    { lockDays: 90, multiplierBps: 15_000 },
+  ];
+  const stakeTier = 0;
 
   // PDAs
   let configPda: PublicKey;
@@ -32,6 +58,26 @@ describe("anchor-nft-staking-q4-25", () => {
   let collectionInfoPda: PublicKey;
   let stakeAccountPda: PublicKey;
   let rewardsAtaPda: PublicKey;
+  let programDataAccount: PublicKey;
+  let boostTablePda: PublicKey;
+  let collectionTreasuryPda: PublicKey;
+  // Partner token paid by a treasury reward stream
+  let partnerMint: PublicKey;
+  let partnerTreasury: PublicKey;
+  let userPartnerAta: PublicKey;
+  // Second pool paying an existing token from a treasury, at rates high
+  // enough to earn whole points within a test
+  const pool = Keypair.generate();
+  const poolAsset = Keypair.generate();
+  let poolInfoPda: PublicKey;
+  let poolTreasuryPda: PublicKey;
+  let poolConfigPda: PublicKey;
+  let poolUserPda: PublicKey;
+  let poolBoostsPda: PublicKey;
+  let poolStakePda: PublicKey;
+  let poolMint: PublicKey;
+  let poolRewardsTreasury: PublicKey;
+  let poolRewardsAta: PublicKey;
 
   console.log(`Admin: ${admin.publicKey.toString()}`);
   console.log(`User: ${user.publicKey.toString()}`);
@@ -45,7 +91,7 @@ describe("anchor-nft-staking-q4-25", () => {
 
     // Derive PDAs
     configPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("config")],
+      [Buffer.from("config"), collection.publicKey.toBuffer()],
       program.programId
     )[0];
     console.log(`Config PDA: ${configPda.toString()}`);
@@ -57,7 +103,7 @@ describe("anchor-nft-staking-q4-25", () => {
     console.log(`Reward Mint PDA: ${rewardMintPda.toString()}`);
 
     userAccountPda = PublicKey.findProgramAddressSync(
-      [Buffer.from("user"), user.publicKey.toBuffer()],
+      [Buffer.from("user"), configPda.toBuffer(), user.publicKey.toBuffer()],
       program.programId
     )[0];
     console.log(`User Account PDA: ${userAccountPda.toString()}`);
@@ -79,18 +125,167 @@ describe("anchor-nft-staking-q4-25", () => {
       user.publicKey
     );
     console.log(`Rewards ATA: ${rewardsAtaPda.toString()}`);
+
+    collectionTreasuryPda = PublicKey.findProgramAddressSync(
+      [Buffer.from("treasury"), collection.publicKey.toBuffer()],
+      program.programId
+    )[0];
+
+    boostTablePda = PublicKey.findProgramAddressSync(
+      [Buffer.from("boosts"), configPda.toBuffer()],
+      program.programId
+    )[0];
+
+    const BPF_LOADER_UPGRADEABLE_PROGRAM_ID = new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111");
+    programDataAccount = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      BPF_LOADER_UPGRADEABLE_PROGRAM_ID
+    )[0];
+  });
+
+  const sleep = (ms: number) =>
+    new Promise((resolve) => setTimeout(resolve, ms));
+
+  // Unix time of the cluster clock when `signature` landed
+  const txTime = async (signature: string) => {
+    await connection.confirmTransaction(signature, "confirmed");
+    const tx = await connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.blockTime;
+  };
+
+  // The asset's attributes plugin, read with mpl-core's deserializer
+  const assetAttributes = async (mint: PublicKey) => {
+    const info = await connection.getAccountInfo(mint);
+    const assetV1 = deserializeAssetV1({
+      publicKey: umiPublicKey(mint.toBase58()),
+      owner: umiPublicKey(info.owner.toBase58()),
+      executable: info.executable,
+      lamports: lamports(info.lamports),
+      data: new Uint8Array(info.data),
+      exists: true,
+    });
+    const value = (key: string) =>
+      assetV1.attributes.attributeList.find((attribute) => attribute.key === key)
+        ?.value;
+    return {
+      authority: assetV1.attributes.authority,
+      stakedAt: Number(value("staked_at")),
+      stakedTime: Number(value("staked_time")),
+    };
+  };
+
+  // Whole points a stake earned by `now`, as `StakeAccount::settle` counts
+  // them from its pool's points index
+  const pointsAt = (stakeAccount: any, config: any, now: number) => {
+    const elapsed = now - config.pointsIndexUpdatedAt.toNumber();
+    const index = config.pointsIndex.add(
+      new anchor.BN(config.pointsPerStake).muln(elapsed)
+    );
+    return index
+      .sub(stakeAccount.pointsIndex)
+      .muln(stakeAccount.multiplierBps)
+      .muln(stakeAccount.boostBps)
+      .div(new anchor.BN(86_400 * 10_000 * 10_000))
+      .toNumber();
+  };
+
+  const stakePdaFor = (mint: PublicKey) =>
+    PublicKey.findProgramAddressSync(
+      [Buffer.from("stake"), configPda.toBuffer(), mint.toBuffer()],
+      program.programId
+    )[0];
+
+  // Mints a fresh asset of the collection to `minter`
+  const mintAsset = async (minter: Keypair | null = user) => {
+    const newAsset = Keypair.generate();
+    await program.methods
+      .mintNft()
+      .accountsStrict({
+        minter: minter ? minter.publicKey : admin.publicKey,
+        asset: newAsset.publicKey,
+        collection: collection.publicKey,
+        collectionInfo: collectionInfoPda,
+        treasury: collectionTreasuryPda,
+        coreProgram: MPL_CORE_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers(minter ? [minter, newAsset] : [newAsset])
+      .rpc();
+    return newAsset.publicKey;
+  };
+
+  // One [asset, stake account] group per asset
+  const stakeGroups = (assets: PublicKey[]) =>
+    assets.flatMap((mint) => [
+      { pubkey: mint, isSigner: false, isWritable: true },
+      { pubkey: stakePdaFor(mint), isSigner: false, isWritable: true },
+    ]);
+
+  describe("Create Collection", () => {
+    it("Create a Metaplex Core collection", async () => {
+      const args = {
+        name: "Test Staking Collection",
+        uri: "https://example.com/collection.json",
+        nftName: "Staked NFT",
+        nftUri: "https://example.com/nft.json",
+        maxSupply: 100,
+        priceLamports: new anchor.BN(10_000_000),
+      };
+
+      const tx = await program.methods
+        .createCollection(args)
+        .accountsStrict({
+          authority: admin.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          treasury: collectionTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([collection])
+        .rpc();
+
+      console.log(`Create Collection tx: ${tx}`);
+
+      const collectionInfo = await program.account.collectionInfo.fetch(
+        collectionInfoPda
+      );
+      assert.equal(
+        collectionInfo.collection.toString(),
+        collection.publicKey.toString()
+      );
+      assert.equal(
+        collectionInfo.authority.toString(),
+        admin.publicKey.toString()
+      );
+      assert.equal(collectionInfo.name, args.name);
+      assert.equal(collectionInfo.nftName, args.nftName);
+      console.log("Collection created successfully");
+    });
   });
 
   describe("Initialize Config", () => {
     it("Initialize the staking config", async () => {
       const tx = await program.methods
-        .initializeConfig(pointsPerStake, maxStake, freezePeriod)
+        .initializeConfig(
+          pointsPerStake,
+          rewardRateBps,
+          maxStake,
+          lockTiers,
+          emissions
+        )
         .accountsStrict({
           admin: admin.publicKey,
+          collectionInfo: collectionInfoPda,
           config: configPda,
           rewardMint: rewardMintPda,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
+          thisProgram: program.programId,
+          programData: programDataAccount,
         })
         .rpc();
 
@@ -98,8 +293,15 @@ describe("anchor-nft-staking-q4-25", () => {
 
       const config = await program.account.stakeConfig.fetch(configPda);
       assert.equal(config.pointsPerStake, pointsPerStake);
+      assert.ok(config.rewardRateBps.eq(rewardRateBps));
+      assert.ok(config.emissions.maxSupply.eq(emissions.maxSupply));
+      assert.ok(config.emissions.minted.isZero());
       assert.equal(config.maxStake, maxStake);
-      assert.equal(config.freezePeriod, freezePeriod);
+      assert.equal(config.lockTiers.length, lockTiers.length);
+      assert.equal(config.lockTiers[2].multiplierBps, lockTiers[2].multiplierBps);
+      assert.equal(config.collection.toString(), collection.publicKey.toString());
+      assert.equal(config.admin.toString(), admin.publicKey.toString());
+      assert.equal(config.paused, false);
       console.log("Config initialized successfully");
     });
   });
@@ -110,6 +312,7 @@ describe("anchor-nft-staking-q4-25", () => {
         .initializeUser()
         .accountsStrict({
           user: user.publicKey,
+          config: configPda,
           userAccount: userAccountPda,
           systemProgram: SystemProgram.programId,
         })
@@ -121,49 +324,190 @@ describe("anchor-nft-staking-q4-25", () => {
       const userAccount = await program.account.userAccount.fetch(
         userAccountPda
       );
-      assert.equal(userAccount.points, 0);
+      assert.ok(userAccount.points.isZero());
       assert.equal(userAccount.amountStaked, 0);
       console.log("User account initialized successfully");
     });
   });
 
-  describe("Create Collection", () => {
-    it("Create a Metaplex Core collection", async () => {
-      const args = {
-        name: "Test Staking Collection",
-        uri: "https://example.com/collection.json",
-        nftName: "Staked NFT",
-        nftUri: "https://example.com/nft.json",
-      };
+  describe("Set Trait Boosts", () => {
+    it("Boost assets minted by the user", async () => {
+      // `mint_nft` writes a Minter attribute, so this boosts the user's NFT
+      const boosts = [
+        { key: "Minter", value: user.publicKey.toString(), multiplierBps: 15_000 },
+      ];
 
       const tx = await program.methods
-        .createCollection(args)
+        .setTraitBoosts(boosts)
         .accountsStrict({
-          authority: admin.publicKey,
-          collection: collection.publicKey,
-          collectionInfo: collectionInfoPda,
-          coreProgram: MPL_CORE_PROGRAM_ID,
+          admin: admin.publicKey,
+          config: configPda,
+          boostTable: boostTablePda,
           systemProgram: SystemProgram.programId,
         })
-        .signers([collection])
         .rpc();
 
-      console.log(`Create Collection tx: ${tx}`);
+      console.log(`Set Trait Boosts tx: ${tx}`);
 
-      const collectionInfo = await program.account.collectionInfo.fetch(
-        collectionInfoPda
-      );
-      assert.equal(
-        collectionInfo.collection.toString(),
-        collection.publicKey.toString()
-      );
-      assert.equal(
-        collectionInfo.authority.toString(),
-        admin.publicKey.toString()
+      const boostTable = await program.account.boostTable.fetch(boostTablePda);
+      assert.equal(boostTable.boosts.length, 1);
+      assert.equal(boostTable.boosts[0].multiplierBps, 15_000);
+    });
+
+    it("Rejects a boost below 1x", async () => {
+      try {
+        await program.methods
+          .setTraitBoosts([
+            { key: "Minter", value: user.publicKey.toString(), multiplierBps: 5_000 },
+          ])
+          .accountsStrict({
+            admin: admin.publicKey,
+            config: configPda,
+            boostTable: boostTablePda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have thrown InvalidTraitBoosts");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidTraitBoosts");
+      }
+    });
+  });
+
+  describe("Add Reward Stream", () => {
+    it("Pay a partner token from a treasury for a week", async () => {
+      const payer = (admin as anchor.Wallet).payer;
+      partnerMint = await createMint(connection, payer, admin.publicKey, null, 6);
+      partnerTreasury = (
+        await getOrCreateAssociatedTokenAccount(
+          connection,
+          payer,
+          partnerMint,
+          configPda,
+          true
+        )
+      ).address;
+      userPartnerAta = (
+        await getOrCreateAssociatedTokenAccount(
+          connection,
+          payer,
+          partnerMint,
+          user.publicKey
+        )
+      ).address;
+      await mintTo(
+        connection,
+        payer,
+        partnerMint,
+        partnerTreasury,
+        payer,
+        1_000_000_000
       );
-      assert.equal(collectionInfo.name, args.name);
-      assert.equal(collectionInfo.nftName, args.nftName);
-      console.log("Collection created successfully");
+
+      const start = Math.floor(Date.now() / 1000) - 60;
+      const tx = await program.methods
+        .addRewardStream(
+          { treasury: {} },
+          new anchor.BN(1_000),
+          new anchor.BN(start),
+          new anchor.BN(start + 7 * 86_400)
+        )
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: configPda,
+          rewardMint: partnerMint,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      console.log(`Add Reward Stream tx: ${tx}`);
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.streams.length, 1);
+      assert.equal(config.streams[0].mint.toString(), partnerMint.toString());
+    });
+
+    it("Rejects a stream of the config's own reward mint", async () => {
+      const start = Math.floor(Date.now() / 1000) - 60;
+      try {
+        await program.methods
+          .addRewardStream(
+            { mint: {} },
+            new anchor.BN(1_000),
+            new anchor.BN(start),
+            new anchor.BN(start + 7 * 86_400)
+          )
+          .accountsStrict({
+            admin: admin.publicKey,
+            config: configPda,
+            rewardMint: rewardMintPda,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          })
+          .rpc();
+        assert.fail("Should have thrown InvalidRewardStream");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidRewardStream");
+      }
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.streams.length, 1);
+    });
+  });
+
+  describe("Set Unstake Penalty", () => {
+    it("Redistribute half of the points of early unstakes", async () => {
+      const tx = await program.methods
+        .setUnstakePenalty(5_000, { redistribute: {} })
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      console.log(`Set Unstake Penalty tx: ${tx}`);
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.earlyUnstakePenaltyBps, 5_000);
+      assert.deepEqual(config.penaltyMode, { redistribute: {} });
+    });
+  });
+
+  describe("Set Streak Tiers", () => {
+    it("Pay 1.1x after a week and 1.25x after a month staked", async () => {
+      const tx = await program.methods
+        .setStreakTiers([
+          { minDays: 7, multiplierBps: 11_000 },
+          { minDays: 30, multiplierBps: 12_500 },
+        ])
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: configPda,
+        })
+        .rpc();
+
+      console.log(`Set Streak Tiers tx: ${tx}`);
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.streakTiers.length, 2);
+      assert.equal(config.streakTiers[1].multiplierBps, 12_500);
+    });
+
+    it("Rejects tiers out of order", async () => {
+      try {
+        await program.methods
+          .setStreakTiers([
+            { minDays: 30, multiplierBps: 12_500 },
+            { minDays: 7, multiplierBps: 11_000 },
+          ])
+          .accountsStrict({
+            admin: admin.publicKey,
+            config: configPda,
+          })
+          .rpc();
+        assert.fail("Should have thrown InvalidStreakTiers");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidStreakTiers");
+      }
     });
   });
 
@@ -176,6 +520,7 @@ describe("anchor-nft-staking-q4-25", () => {
           asset: asset.publicKey,
           collection: collection.publicKey,
           collectionInfo: collectionInfoPda,
+          treasury: collectionTreasuryPda,
           coreProgram: MPL_CORE_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -187,10 +532,168 @@ describe("anchor-nft-staking-q4-25", () => {
       // Verify asset exists
       const assetAccount = await connection.getAccountInfo(asset.publicKey);
       assert.ok(assetAccount, "Asset should exist");
+
+      const collectionInfo = await program.account.collectionInfo.fetch(
+        collectionInfoPda
+      );
+      assert.equal(collectionInfo.minted, 1);
       console.log("NFT minted successfully (no freeze at mint)");
     });
   });
 
+  describe("Update Config", () => {
+    const newAdmin = Keypair.generate();
+
+    it("Rejects a signer that isn't the admin", async () => {
+      try {
+        await program.methods
+          .pause(true)
+          .accountsStrict({ admin: user.publicKey, config: configPda })
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown NotAuthorized");
+      } catch (err) {
+        assert.include(err.toString(), "NotAuthorized");
+      }
+    });
+
+    it("Blocks new stakes while paused", async () => {
+      await program.methods
+        .pause(true)
+        .accountsStrict({ admin: admin.publicKey, config: configPda })
+        .rpc();
+
+      try {
+        await program.methods
+          .stake(stakeTier)
+          .accountsStrict({
+            user: user.publicKey,
+            asset: asset.publicKey,
+            collection: collection.publicKey,
+            collectionInfo: collectionInfoPda,
+            stakeAccount: stakeAccountPda,
+            boostTable: boostTablePda,
+            config: configPda,
+            userAccount: userAccountPda,
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown StakingPaused");
+      } catch (err) {
+        assert.include(err.toString(), "StakingPaused");
+      }
+
+      await program.methods
+        .pause(false)
+        .accountsStrict({ admin: admin.publicKey, config: configPda })
+        .rpc();
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.paused, false);
+    });
+
+    it("Applies a new points rate only from when it is set", async () => {
+      const before = await program.account.stakeConfig.fetch(configPda);
+
+      const tx = await program.methods
+        .updateConfig(pointsPerStake * 2, rewardRateBps, maxStake, lockTiers)
+        .accountsStrict({ admin: admin.publicKey, config: configPda })
+        .rpc();
+
+      console.log(`Update Config tx: ${tx}`);
+
+      // Everything up to the change is checkpointed at the old rate
+      const now = await txTime(tx);
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.pointsPerStake, pointsPerStake * 2);
+      assert.equal(config.pointsIndexUpdatedAt.toNumber(), now);
+      assert.ok(
+        config.pointsIndex.eq(
+          before.pointsIndex.addn(
+            pointsPerStake * (now - before.pointsIndexUpdatedAt.toNumber())
+          )
+        )
+      );
+    });
+
+    it("Hands the admin role over and back", async () => {
+      await program.methods
+        .transferAdmin(newAdmin.publicKey)
+        .accountsStrict({ admin: admin.publicKey, config: configPda })
+        .rpc();
+
+      // Only the pending admin can accept
+      try {
+        await program.methods
+          .acceptAdmin()
+          .accountsStrict({ newAdmin: user.publicKey, config: configPda })
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown NotAuthorized");
+      } catch (err) {
+        assert.include(err.toString(), "NotAuthorized");
+      }
+
+      await program.methods
+        .acceptAdmin()
+        .accountsStrict({ newAdmin: newAdmin.publicKey, config: configPda })
+        .signers([newAdmin])
+        .rpc();
+      let config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.admin.toString(), newAdmin.publicKey.toString());
+      assert.isNull(config.pendingAdmin);
+
+      await program.methods
+        .transferAdmin(admin.publicKey)
+        .accountsStrict({ admin: newAdmin.publicKey, config: configPda })
+        .signers([newAdmin])
+        .rpc();
+      await program.methods
+        .acceptAdmin()
+        .accountsStrict({ newAdmin: admin.publicKey, config: configPda })
+        .rpc();
+      config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.admin.toString(), admin.publicKey.toString());
+    });
+  });
+
+  describe("Withdraw Proceeds", () => {
+    it("Send the mint price to the collection authority", async () => {
+      const tx = await program.methods
+        .withdrawProceeds()
+        .accountsStrict({
+          authority: admin.publicKey,
+          collectionInfo: collectionInfoPda,
+          treasury: collectionTreasuryPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+
+      console.log(`Withdraw Proceeds tx: ${tx}`);
+
+      const rent = await connection.getMinimumBalanceForRentExemption(0);
+      assert.equal(await connection.getBalance(collectionTreasuryPda), rent);
+    });
+
+    it("Rejects a withdrawal with nothing to send", async () => {
+      try {
+        await program.methods
+          .withdrawProceeds()
+          .accountsStrict({
+            authority: admin.publicKey,
+            collectionInfo: collectionInfoPda,
+            treasury: collectionTreasuryPda,
+            systemProgram: SystemProgram.programId,
+          })
+          .rpc();
+        assert.fail("Should have thrown NoProceeds");
+      } catch (err) {
+        assert.include(err.toString(), "NoProceeds");
+      }
+    });
+  });
+
   describe("Stake NFT", () => {
     it("Stake the NFT and add FreezeDelegate plugin", async () => {
       const userAccountBefore = await program.account.userAccount.fetch(
@@ -199,12 +702,14 @@ describe("anchor-nft-staking-q4-25", () => {
       assert.equal(userAccountBefore.amountStaked, 0);
 
       const tx = await program.methods
-        .stake()
+        .stake(stakeTier)
         .accountsStrict({
           user: user.publicKey,
           asset: asset.publicKey,
           collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
           stakeAccount: stakeAccountPda,
+          boostTable: boostTablePda,
           config: configPda,
           userAccount: userAccountPda,
           coreProgram: MPL_CORE_PROGRAM_ID,
@@ -221,21 +726,31 @@ describe("anchor-nft-staking-q4-25", () => {
       assert.equal(stakeAccount.owner.toString(), user.publicKey.toString());
       assert.equal(stakeAccount.mint.toString(), asset.publicKey.toString());
       assert.ok(stakeAccount.stakedAt > new anchor.BN(0));
+      assert.ok(stakeAccount.lastClaimedAt.eq(stakeAccount.stakedAt));
+      assert.equal(stakeAccount.tier, stakeTier);
+      assert.equal(stakeAccount.boostBps, 15_000);
+      assert.ok(stakeAccount.unlockAt.eq(stakeAccount.stakedAt));
 
       const userAccountAfter = await program.account.userAccount.fetch(
         userAccountPda
       );
       assert.equal(userAccountAfter.amountStaked, 1);
+      assert.ok(userAccountAfter.streakStart.gtn(0));
+      assert.equal(userAccountAfter.streakDays, 0);
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.totalStaked, 1);
       console.log("NFT staked successfully");
     });
   });
 
   describe("Unstake NFT", () => {
     it("Unstake the NFT and remove FreezeDelegate plugin", async () => {
-      // Wait for freeze period if needed (0 in this test)
-      if (freezePeriod > 0) {
-        console.log(`Waiting for freeze period: ${freezePeriod} days`);
-        // In a real test with actual freeze period, you'd need to manipulate time or wait
+      // Wait for the lock period if needed (0 for tier 0)
+      const lockDays = lockTiers[stakeTier].lockDays;
+      if (lockDays > 0) {
+        console.log(`Waiting for lock period: ${lockDays} days`);
+        // In a real test with an actual lock period, you'd need to manipulate time or wait
       }
 
       const userAccountBefore = await program.account.userAccount.fetch(
@@ -273,11 +788,14 @@ describe("anchor-nft-staking-q4-25", () => {
         userAccountPda
       );
       assert.equal(userAccountAfter.amountStaked, 0);
+      // Unstaking the last NFT ends the streak
+      assert.ok(userAccountAfter.streakStart.isZero());
+      assert.equal(userAccountAfter.streakDays, 0);
       // Points should be awarded (time_elapsed * points_per_stake)
-      assert.ok(userAccountAfter.points >= pointsBefore);
+      assert.ok(userAccountAfter.points.gte(pointsBefore));
       console.log(
         `NFT unstaked successfully, points earned: ${
-          userAccountAfter.points - pointsBefore
+          userAccountAfter.points.sub(pointsBefore).toString()
         }`
       );
     });
@@ -290,7 +808,7 @@ describe("anchor-nft-staking-q4-25", () => {
       );
       const pointsBefore = userAccountBefore.points;
 
-      if (pointsBefore === 0) {
+      if (pointsBefore.isZero()) {
         console.log("No points to claim, skipping claim test");
         return;
       }
@@ -303,10 +821,17 @@ describe("anchor-nft-staking-q4-25", () => {
           config: configPda,
           userAccount: userAccountPda,
           rewardMint: rewardMintPda,
+          treasury: null,
           associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
           tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
+        // One [mint, user token account, treasury] group per stream
+        .remainingAccounts([
+          { pubkey: partnerMint, isSigner: false, isWritable: false },
+          { pubkey: userPartnerAta, isSigner: false, isWritable: true },
+          { pubkey: partnerTreasury, isSigner: false, isWritable: true },
+        ])
         .signers([user])
         .rpc();
 
@@ -315,7 +840,10 @@ describe("anchor-nft-staking-q4-25", () => {
       const userAccountAfter = await program.account.userAccount.fetch(
         userAccountPda
       );
-      assert.equal(userAccountAfter.points, 0, "Points should be reset to 0");
+      assert.ok(userAccountAfter.points.isZero(), "Points should be reset to 0");
+
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.ok(config.emissions.minted.gtn(0), "Minted amount should be tracked");
 
       // Check token balance
       const rewardsAta = await connection.getAccountInfo(rewardsAtaPda);
@@ -323,4 +851,1152 @@ describe("anchor-nft-staking-q4-25", () => {
       console.log("Rewards claimed successfully");
     });
   });
+
+  describe("On-Asset Staking", () => {
+    it("Stake with the staking state written to the asset", async () => {
+      await program.methods
+        .mintNft()
+        .accountsStrict({
+          minter: user.publicKey,
+          asset: onAsset.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          treasury: collectionTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user, onAsset])
+        .rpc();
+
+      const tx = await program.methods
+        .stakeOnAsset()
+        .accountsStrict({
+          user: user.publicKey,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          asset: onAsset.publicKey,
+          config: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      console.log(`Stake On Asset tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 1);
+
+      // Only the pool can rewrite the staking attributes
+      const attributes = await assetAttributes(onAsset.publicKey);
+      assert.deepEqual(attributes.authority, {
+        type: "Address",
+        address: umiPublicKey(configPda.toBase58()),
+      });
+      assert.equal(attributes.stakedAt, await txTime(tx));
+      assert.equal(attributes.stakedTime, 0);
+    });
+
+    it("Unstake and keep the accumulated staking time", async () => {
+      const { stakedAt } = await assetAttributes(onAsset.publicKey);
+
+      const tx = await program.methods
+        .unstakeOnAsset()
+        .accountsStrict({
+          user: user.publicKey,
+          collection: collection.publicKey,
+          asset: onAsset.publicKey,
+          config: configPda,
+          userAccount: userAccountPda,
+          boostTable: boostTablePda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      console.log(`Unstake On Asset tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 0);
+
+      const attributes = await assetAttributes(onAsset.publicKey);
+      assert.equal(attributes.stakedAt, 0);
+      assert.equal(attributes.stakedTime, (await txTime(tx)) - stakedAt);
+    });
+  });
+
+  describe("Stake Many", () => {
+    const batch: PublicKey[] = [];
+
+    const stakeManyAccounts = () => ({
+      user: user.publicKey,
+      collection: collection.publicKey,
+      collectionInfo: collectionInfoPda,
+      config: configPda,
+      userAccount: userAccountPda,
+      boostTable: boostTablePda,
+      coreProgram: MPL_CORE_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      for (let i = 0; i < maxStake; i++) {
+        batch.push(await mintAsset());
+      }
+    });
+
+    it("Rolls back the whole batch if one asset isn't the user's", async () => {
+      // Minted by the admin, so the user doesn't own it
+      const foreign = await mintAsset(null);
+
+      try {
+        await program.methods
+          .stakeMany(stakeTier)
+          .accountsStrict(stakeManyAccounts())
+          .remainingAccounts(stakeGroups([batch[0], foreign]))
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown NotOwner");
+      } catch (err) {
+        assert.include(err.toString(), "NotOwner");
+      }
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 0);
+      assert.isNull(await connection.getAccountInfo(stakePdaFor(batch[0])));
+    });
+
+    it("Stake up to max_stake, including a pre-funded stake account", async () => {
+      // Lamports sent to a stake PDA ahead of time must not block staking
+      await provider.sendAndConfirm(
+        new anchor.web3.Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: admin.publicKey,
+            toPubkey: stakePdaFor(batch[0]),
+            lamports: 1_000_000,
+          })
+        )
+      );
+      const configBefore = await program.account.stakeConfig.fetch(configPda);
+
+      const tx = await program.methods
+        .stakeMany(stakeTier)
+        .accountsStrict(stakeManyAccounts())
+        .remainingAccounts(stakeGroups(batch))
+        .signers([user])
+        .rpc();
+
+      console.log(`Stake Many tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, maxStake);
+      const config = await program.account.stakeConfig.fetch(configPda);
+      assert.equal(config.totalStaked, configBefore.totalStaked + maxStake);
+      for (const mint of batch) {
+        const stakeAccount = await program.account.stakeAccount.fetch(
+          stakePdaFor(mint)
+        );
+        assert.equal(stakeAccount.mint.toString(), mint.toString());
+        assert.equal(stakeAccount.owner.toString(), user.publicKey.toString());
+      }
+    });
+
+    it("Rejects staking past max_stake", async () => {
+      const extra = await mintAsset();
+
+      try {
+        await program.methods
+          .stakeMany(stakeTier)
+          .accountsStrict(stakeManyAccounts())
+          .remainingAccounts(stakeGroups([extra]))
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown MaxStakeReached");
+      } catch (err) {
+        assert.include(err.toString(), "MaxStakeReached");
+      }
+    });
+
+    it("Unstake the whole batch", async () => {
+      const tx = await program.methods
+        .unstakeMany()
+        .accountsStrict({
+          user: user.publicKey,
+          collection: collection.publicKey,
+          config: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(stakeGroups(batch))
+        .signers([user])
+        .rpc();
+
+      console.log(`Unstake Many tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        userAccountPda
+      );
+      assert.equal(userAccount.amountStaked, 0);
+      for (const mint of batch) {
+        assert.isNull(await connection.getAccountInfo(stakePdaFor(mint)));
+      }
+    });
+
+    it("Leaving out the boost table only forfeits the boost", async () => {
+      // Minted by the user, so the table would boost it 1.5x
+      const mint = await mintAsset();
+      await program.methods
+        .stake(stakeTier)
+        .accountsStrict({
+          user: user.publicKey,
+          asset: mint,
+          collection: collection.publicKey,
+          collectionInfo: collectionInfoPda,
+          stakeAccount: stakePdaFor(mint),
+          boostTable: null,
+          config: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        stakePdaFor(mint)
+      );
+      assert.equal(stakeAccount.boostBps, 10_000);
+
+      await program.methods
+        .unstake()
+        .accountsStrict({
+          user: user.publicKey,
+          asset: mint,
+          collection: collection.publicKey,
+          stakeAccount: stakePdaFor(mint),
+          config: configPda,
+          userAccount: userAccountPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    });
+  });
+
+  describe("Migrate User", () => {
+    // Owns the account in tests/fixtures/legacy-user.json, which the
+    // validator loads in the legacy single pool's layout: 1_234 points and
+    // nothing staked
+    const legacyUser = Keypair.fromSeed(new Uint8Array(32).fill(43));
+    const legacyPoints = 1_234;
+    let legacyUserPda: PublicKey;
+    let migratedUserPda: PublicKey;
+
+    const migrate = (signer: Keypair, legacyAccount: PublicKey) =>
+      program.methods
+        .migrateUser()
+        .accountsStrict({
+          user: signer.publicKey,
+          config: configPda,
+          legacyUserAccount: legacyAccount,
+          userAccount: PublicKey.findProgramAddressSync(
+            [
+              Buffer.from("user"),
+              configPda.toBuffer(),
+              signer.publicKey.toBuffer(),
+            ],
+            program.programId
+          )[0],
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([signer])
+        .rpc();
+
+    before(async () => {
+      legacyUserPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), legacyUser.publicKey.toBuffer()],
+        program.programId
+      )[0];
+      migratedUserPda = PublicKey.findProgramAddressSync(
+        [
+          Buffer.from("user"),
+          configPda.toBuffer(),
+          legacyUser.publicKey.toBuffer(),
+        ],
+        program.programId
+      )[0];
+      await connection.confirmTransaction(
+        await connection.requestAirdrop(legacyUser.publicKey, 1_000_000_000),
+        "confirmed"
+      );
+    });
+
+    it("Carry a legacy account's points into the pool", async () => {
+      const legacyLamports = (await connection.getAccountInfo(legacyUserPda))
+        .lamports;
+      const balanceBefore = await connection.getBalance(legacyUser.publicKey);
+
+      const tx = await migrate(legacyUser, legacyUserPda);
+
+      console.log(`Migrate User tx: ${tx}`);
+
+      const userAccount = await program.account.userAccount.fetch(
+        migratedUserPda
+      );
+      assert.equal(userAccount.config.toString(), configPda.toString());
+      assert.equal(userAccount.points.toNumber(), legacyPoints);
+      assert.equal(userAccount.amountStaked, 0);
+      assert.ok(userAccount.streakStart.isZero());
+
+      // The legacy account is closed and its rent refunded
+      assert.isNull(await connection.getAccountInfo(legacyUserPda));
+      const rent = (await connection.getAccountInfo(migratedUserPda)).lamports;
+      assert.equal(
+        await connection.getBalance(legacyUser.publicKey),
+        balanceBefore + legacyLamports - rent
+      );
+    });
+
+    it("Rejects a user without a legacy account", async () => {
+      const newcomer = Keypair.generate();
+      await connection.confirmTransaction(
+        await connection.requestAirdrop(newcomer.publicKey, 1_000_000_000),
+        "confirmed"
+      );
+      const newcomerLegacyPda = PublicKey.findProgramAddressSync(
+        [Buffer.from("user"), newcomer.publicKey.toBuffer()],
+        program.programId
+      )[0];
+
+      try {
+        await migrate(newcomer, newcomerLegacyPda);
+        assert.fail("Should have thrown AlreadyMigrated");
+      } catch (err) {
+        assert.include(err.toString(), "AlreadyMigrated");
+      }
+    });
+  });
+
+  describe("Treasury Rewards", () => {
+    // 255 points a day at 6.5x lock and 6.5x trait boost, a point every ~8s.
+    // Tier 1 is locked, for early unstakes
+    const poolLockTiers = [
+      { lockDays: 0, multiplierBps: 65_535 },
+      { lockDays: 7, multiplierBps: 65_535 },
+    ];
+
+    const claimAccounts = () => ({
+      user: user.publicKey,
+      rewardsAta: poolRewardsAta,
+      config: poolConfigPda,
+      userAccount: poolUserPda,
+      rewardMint: poolMint,
+      treasury: poolRewardsTreasury,
+      associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+      tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+      systemProgram: SystemProgram.programId,
+    });
+
+    before(async () => {
+      const findPda = (seeds: Buffer[]) =>
+        PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      poolInfoPda = findPda([
+        Buffer.from("collection_info"),
+        pool.publicKey.toBuffer(),
+      ]);
+      poolTreasuryPda = findPda([
+        Buffer.from("treasury"),
+        pool.publicKey.toBuffer(),
+      ]);
+      poolConfigPda = findPda([Buffer.from("config"), pool.publicKey.toBuffer()]);
+      poolUserPda = findPda([
+        Buffer.from("user"),
+        poolConfigPda.toBuffer(),
+        user.publicKey.toBuffer(),
+      ]);
+      poolBoostsPda = findPda([Buffer.from("boosts"), poolConfigPda.toBuffer()]);
+      poolStakePda = findPda([
+        Buffer.from("stake"),
+        poolConfigPda.toBuffer(),
+        poolAsset.publicKey.toBuffer(),
+      ]);
+
+      // No decimals, so one point pays one token at a 10_000 bps rate
+      const payer = (admin as anchor.Wallet).payer;
+      poolMint = await createMint(connection, payer, admin.publicKey, null, 0);
+      poolRewardsTreasury = getAssociatedTokenAddressSync(
+        poolMint,
+        poolConfigPda,
+        true
+      );
+      poolRewardsAta = getAssociatedTokenAddressSync(poolMint, user.publicKey);
+
+      await program.methods
+        .createCollection({
+          name: "Treasury Pool",
+          uri: "https://example.com/pool.json",
+          nftName: "Pool NFT",
+          nftUri: "https://example.com/pool-nft.json",
+          maxSupply: 10,
+          priceLamports: new anchor.BN(0),
+        })
+        .accountsStrict({
+          authority: admin.publicKey,
+          collection: pool.publicKey,
+          collectionInfo: poolInfoPda,
+          treasury: poolTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([pool])
+        .rpc();
+    });
+
+    it("Initialize a pool paying from a treasury", async () => {
+      const tx = await program.methods
+        .initializeTreasuryConfig(
+          255,
+          new anchor.BN(10_000),
+          maxStake,
+          poolLockTiers,
+          emissions
+        )
+        .accountsStrict({
+          admin: admin.publicKey,
+          collectionInfo: poolInfoPda,
+          config: poolConfigPda,
+          rewardMint: poolMint,
+          treasury: poolRewardsTreasury,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          thisProgram: program.programId,
+          programData: programDataAccount,
+        })
+        .rpc();
+
+      console.log(`Initialize Treasury Config tx: ${tx}`);
+
+      const config = await program.account.stakeConfig.fetch(poolConfigPda);
+      assert.deepEqual(config.rewardMode, { treasury: {} });
+      assert.equal(config.rewardMint.toString(), poolMint.toString());
+      const treasury = await connection.getTokenAccountBalance(
+        poolRewardsTreasury
+      );
+      assert.equal(treasury.value.amount, "0");
+
+      await program.methods
+        .initializeUser()
+        .accountsStrict({
+          user: user.publicKey,
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .setTraitBoosts([
+          {
+            key: "Minter",
+            value: user.publicKey.toString(),
+            multiplierBps: 65_535,
+          },
+        ])
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: poolConfigPda,
+          boostTable: poolBoostsPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .mintNft()
+        .accountsStrict({
+          minter: user.publicKey,
+          asset: poolAsset.publicKey,
+          collection: pool.publicKey,
+          collectionInfo: poolInfoPda,
+          treasury: poolTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user, poolAsset])
+        .rpc();
+      await program.methods
+        .stake(0)
+        .accountsStrict({
+          user: user.publicKey,
+          asset: poolAsset.publicKey,
+          collection: pool.publicKey,
+          collectionInfo: poolInfoPda,
+          stakeAccount: poolStakePda,
+          boostTable: poolBoostsPda,
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    });
+
+    it("Rejects staking an asset of another collection", async () => {
+      // The pool's asset, staked into the first collection's pool
+      try {
+        await program.methods
+          .stake(stakeTier)
+          .accountsStrict({
+            user: user.publicKey,
+            asset: poolAsset.publicKey,
+            collection: collection.publicKey,
+            collectionInfo: collectionInfoPda,
+            stakeAccount: stakePdaFor(poolAsset.publicKey),
+            boostTable: boostTablePda,
+            config: configPda,
+            userAccount: userAccountPda,
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown InvalidCollection");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidCollection");
+      }
+    });
+
+    it("Rejects a claim the treasury can't cover", async () => {
+      // Long enough to earn at least one whole point
+      await sleep(10_000);
+
+      try {
+        await program.methods
+          .claim()
+          .accountsStrict(claimAccounts())
+          .remainingAccounts([
+            { pubkey: poolStakePda, isSigner: false, isWritable: true },
+          ])
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown InsufficientTreasury");
+      } catch (err) {
+        assert.include(err.toString(), "InsufficientTreasury");
+      }
+    });
+
+    it("Fund the treasury and claim from it", async () => {
+      const payer = (admin as anchor.Wallet).payer;
+      const funderAta = (
+        await getOrCreateAssociatedTokenAccount(
+          connection,
+          payer,
+          poolMint,
+          admin.publicKey
+        )
+      ).address;
+      await mintTo(connection, payer, poolMint, funderAta, payer, 1_000_000);
+
+      const fundTx = await program.methods
+        .fundRewards(new anchor.BN(1_000_000))
+        .accountsStrict({
+          funder: admin.publicKey,
+          config: poolConfigPda,
+          rewardMint: poolMint,
+          funderAta,
+          treasury: poolRewardsTreasury,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+      console.log(`Fund Rewards tx: ${fundTx}`);
+
+      const claimTx = await program.methods
+        .claim()
+        .accountsStrict(claimAccounts())
+        .remainingAccounts([
+          { pubkey: poolStakePda, isSigner: false, isWritable: true },
+        ])
+        .signers([user])
+        .rpc();
+
+      console.log(`Claim tx: ${claimTx}`);
+
+      const paid = Number(
+        (await connection.getTokenAccountBalance(poolRewardsAta)).value.amount
+      );
+      assert.isAbove(paid, 0);
+      const treasury = await connection.getTokenAccountBalance(
+        poolRewardsTreasury
+      );
+      assert.equal(Number(treasury.value.amount), 1_000_000 - paid);
+
+      const config = await program.account.stakeConfig.fetch(poolConfigPda);
+      assert.equal(config.emissions.minted.toNumber(), paid);
+      const userAccount = await program.account.userAccount.fetch(poolUserPda);
+      assert.ok(userAccount.points.isZero());
+    });
+  });
+
+  describe("Emergency Unstake", () => {
+    const PRECISION = new anchor.BN(1_000_000_000_000);
+
+    const poolStakePdaFor = (mint: PublicKey) =>
+      PublicKey.findProgramAddressSync(
+        [Buffer.from("stake"), poolConfigPda.toBuffer(), mint.toBuffer()],
+        program.programId
+      )[0];
+
+    const setPenalty = (mode: object) =>
+      program.methods
+        .setUnstakePenalty(5_000, mode as any)
+        .accountsStrict({ admin: admin.publicKey, config: poolConfigPda })
+        .rpc();
+
+    const stakeInPool = async (tier: number) => {
+      const newAsset = Keypair.generate();
+      await program.methods
+        .mintNft()
+        .accountsStrict({
+          minter: user.publicKey,
+          asset: newAsset.publicKey,
+          collection: pool.publicKey,
+          collectionInfo: poolInfoPda,
+          treasury: poolTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user, newAsset])
+        .rpc();
+      await program.methods
+        .stake(tier)
+        .accountsStrict({
+          user: user.publicKey,
+          asset: newAsset.publicKey,
+          collection: pool.publicKey,
+          collectionInfo: poolInfoPda,
+          stakeAccount: poolStakePdaFor(newAsset.publicKey),
+          boostTable: poolBoostsPda,
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+      return newAsset.publicKey;
+    };
+
+    const emergencyUnstake = (mint: PublicKey) =>
+      program.methods
+        .emergencyUnstake()
+        .accountsStrict({
+          user: user.publicKey,
+          collection: pool.publicKey,
+          asset: mint,
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          stakeAccount: poolStakePdaFor(mint),
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+    it("Rejects a lock tier the pool doesn't have", async () => {
+      // The pool only has tiers 0 and 1
+      try {
+        await stakeInPool(2);
+        assert.fail("Should have thrown InvalidLockTier");
+      } catch (err) {
+        assert.include(err.toString(), "InvalidLockTier");
+      }
+    });
+
+    it("Burn half the points of a locked stake", async () => {
+      await setPenalty({ burn: {} });
+      const locked = await stakeInPool(1);
+      // A regular unstake has to wait for the lock
+      try {
+        await program.methods
+          .unstake()
+          .accountsStrict({
+            user: user.publicKey,
+            asset: locked,
+            collection: pool.publicKey,
+            stakeAccount: poolStakePdaFor(locked),
+            config: poolConfigPda,
+            userAccount: poolUserPda,
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown LockPeriodNotPassed");
+      } catch (err) {
+        assert.include(err.toString(), "LockPeriodNotPassed");
+      }
+      // Long enough to earn a few whole points
+      await sleep(20_000);
+
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        poolStakePdaFor(locked)
+      );
+      const userBefore = await program.account.userAccount.fetch(poolUserPda);
+      const configBefore = await program.account.stakeConfig.fetch(
+        poolConfigPda
+      );
+
+      const tx = await emergencyUnstake(locked);
+      console.log(`Emergency Unstake tx: ${tx}`);
+
+      const points = pointsAt(stakeAccount, configBefore, await txTime(tx));
+      const penalty = Math.floor(points / 2);
+      assert.isAbove(penalty, 0);
+      const userAfter = await program.account.userAccount.fetch(poolUserPda);
+      assert.equal(
+        userAfter.points.sub(userBefore.points).toNumber(),
+        points - penalty
+      );
+      // Burned, nobody else's share grows
+      const configAfter = await program.account.stakeConfig.fetch(
+        poolConfigPda
+      );
+      assert.ok(configAfter.penaltyIndex.eq(configBefore.penaltyIndex));
+    });
+
+    it("Can't claim a locked stake to dodge the penalty", async () => {
+      const locked = await stakeInPool(1);
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        poolStakePdaFor(locked)
+      );
+      // Long enough to earn a few whole points
+      await sleep(20_000);
+
+      try {
+        await program.methods
+          .claim()
+          .accountsStrict({
+            user: user.publicKey,
+            rewardsAta: poolRewardsAta,
+            config: poolConfigPda,
+            userAccount: poolUserPda,
+            rewardMint: poolMint,
+            treasury: poolRewardsTreasury,
+            associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+            tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .remainingAccounts([
+            {
+              pubkey: poolStakePdaFor(locked),
+              isSigner: false,
+              isWritable: true,
+            },
+          ])
+          .signers([user])
+          .rpc();
+        assert.fail("Should have thrown LockPeriodNotPassed");
+      } catch (err) {
+        assert.include(err.toString(), "LockPeriodNotPassed");
+      }
+
+      const config = await program.account.stakeConfig.fetch(poolConfigPda);
+      const userBefore = await program.account.userAccount.fetch(poolUserPda);
+      const tx = await emergencyUnstake(locked);
+
+      // Everything since staking is still there to be penalized
+      const points = pointsAt(stakeAccount, config, await txTime(tx));
+      const penalty = Math.floor(points / 2);
+      assert.isAbove(penalty, 0);
+      const userAfter = await program.account.userAccount.fetch(poolUserPda);
+      assert.equal(
+        userAfter.points.sub(userBefore.points).toNumber(),
+        points - penalty
+      );
+    });
+
+    it("Redistribute it, without paying a stake that just arrived", async () => {
+      await setPenalty({ redistribute: {} });
+      const locked = await stakeInPool(1);
+      // Enough points for a penalty that still leaves a whole point per
+      // remaining stake
+      await sleep(40_000);
+      // Staked right before the early unstake, then straight back out
+      const newcomer = await stakeInPool(0);
+
+      const lockedAccount = await program.account.stakeAccount.fetch(
+        poolStakePdaFor(locked)
+      );
+      const userBefore = await program.account.userAccount.fetch(poolUserPda);
+      const configBefore = await program.account.stakeConfig.fetch(
+        poolConfigPda
+      );
+      // The pool's first asset and the newcomer
+      assert.equal(configBefore.totalStaked, 3);
+
+      const tx = await emergencyUnstake(locked);
+      console.log(`Emergency Unstake tx: ${tx}`);
+
+      const points = pointsAt(lockedAccount, configBefore, await txTime(tx));
+      const penalty = Math.floor(points / 2);
+      assert.isAtLeast(penalty, 2);
+      const userMid = await program.account.userAccount.fetch(poolUserPda);
+      assert.equal(
+        userMid.points.sub(userBefore.points).toNumber(),
+        points - penalty
+      );
+      const configMid = await program.account.stakeConfig.fetch(poolConfigPda);
+      const perStake = new anchor.BN(penalty).mul(PRECISION).divn(2);
+      assert.ok(
+        configMid.penaltyIndex.eq(configBefore.penaltyIndex.add(perStake))
+      );
+
+      const newcomerAccount = await program.account.stakeAccount.fetch(
+        poolStakePdaFor(newcomer)
+      );
+      const unstakeTx = await program.methods
+        .unstake()
+        .accountsStrict({
+          user: user.publicKey,
+          asset: newcomer,
+          collection: pool.publicKey,
+          stakeAccount: poolStakePdaFor(newcomer),
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      // Only its own points, its share hadn't vested yet
+      const userAfter = await program.account.userAccount.fetch(poolUserPda);
+      assert.equal(
+        userAfter.points.sub(userMid.points).toNumber(),
+        pointsAt(newcomerAccount, configMid, await txTime(unstakeTx))
+      );
+      // and goes to the one asset left instead
+      const forfeited = configMid.penaltyIndex
+        .sub(newcomerAccount.penaltyIndex)
+        .div(PRECISION);
+      assert.isAbove(forfeited.toNumber(), 0);
+      const configAfter = await program.account.stakeConfig.fetch(
+        poolConfigPda
+      );
+      assert.ok(
+        configAfter.penaltyIndex.eq(
+          configMid.penaltyIndex.add(forfeited.mul(PRECISION))
+        )
+      );
+      assert.equal(configAfter.totalStaked, 1);
+    });
+  });
+
+  describe("Streak Bonus", () => {
+    it("Double the points of a streak from its first day", async () => {
+      await program.methods
+        .setStreakTiers([
+          { minDays: 0, multiplierBps: 20_000 },
+          { minDays: 7, multiplierBps: 30_000 },
+        ])
+        .accountsStrict({ admin: admin.publicKey, config: poolConfigPda })
+        .rpc();
+      // The pool's first asset has been staked since the treasury tests
+      await sleep(10_000);
+
+      const stakeAccount = await program.account.stakeAccount.fetch(
+        poolStakePda
+      );
+      const config = await program.account.stakeConfig.fetch(poolConfigPda);
+      const userBefore = await program.account.userAccount.fetch(poolUserPda);
+
+      const tx = await program.methods
+        .unstake()
+        .accountsStrict({
+          user: user.publicKey,
+          asset: poolAsset.publicKey,
+          collection: pool.publicKey,
+          stakeAccount: poolStakePda,
+          config: poolConfigPda,
+          userAccount: poolUserPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+
+      console.log(`Unstake tx: ${tx}`);
+
+      // Only the first tier is reached, the 7 day one doesn't count yet
+      const points = pointsAt(stakeAccount, config, await txTime(tx));
+      assert.isAbove(points, 0);
+      const userAfter = await program.account.userAccount.fetch(poolUserPda);
+      assert.equal(
+        userAfter.points.sub(userBefore.points).toNumber(),
+        2 * points
+      );
+      assert.ok(userAfter.streakStart.isZero());
+    });
+  });
+
+  describe("Emissions Cap", () => {
+    // A minting pool at the second pool's rates, capped at one point's worth
+    // of its 6 decimal reward token: 100 base units at 1 bps
+    const capped = Keypair.generate();
+    const cappedAsset = Keypair.generate();
+    const cappedRateBps = new anchor.BN(1);
+    const cappedSupply = new anchor.BN(100);
+    const daoAuthority = Keypair.generate();
+    let cappedInfoPda: PublicKey;
+    let cappedTreasuryPda: PublicKey;
+    let cappedConfigPda: PublicKey;
+    let cappedMintPda: PublicKey;
+    let cappedUserPda: PublicKey;
+    let cappedBoostsPda: PublicKey;
+    let cappedStakePda: PublicKey;
+
+    const claim = () =>
+      program.methods
+        .claim()
+        .accountsStrict({
+          user: user.publicKey,
+          rewardsAta: getAssociatedTokenAddressSync(
+            cappedMintPda,
+            user.publicKey
+          ),
+          config: cappedConfigPda,
+          userAccount: cappedUserPda,
+          rewardMint: cappedMintPda,
+          treasury: null,
+          associatedTokenProgram: anchor.utils.token.ASSOCIATED_PROGRAM_ID,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts([
+          { pubkey: cappedStakePda, isSigner: false, isWritable: true },
+        ])
+        .signers([user])
+        .rpc();
+
+    const releaseMintAuthority = () =>
+      program.methods
+        .releaseMintAuthority(daoAuthority.publicKey)
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: cappedConfigPda,
+          rewardMint: cappedMintPda,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+        })
+        .rpc();
+
+    before(async () => {
+      const findPda = (seeds: Buffer[]) =>
+        PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+      cappedInfoPda = findPda([
+        Buffer.from("collection_info"),
+        capped.publicKey.toBuffer(),
+      ]);
+      cappedTreasuryPda = findPda([
+        Buffer.from("treasury"),
+        capped.publicKey.toBuffer(),
+      ]);
+      cappedConfigPda = findPda([
+        Buffer.from("config"),
+        capped.publicKey.toBuffer(),
+      ]);
+      cappedMintPda = findPda([Buffer.from("rewards"), cappedConfigPda.toBuffer()]);
+      cappedUserPda = findPda([
+        Buffer.from("user"),
+        cappedConfigPda.toBuffer(),
+        user.publicKey.toBuffer(),
+      ]);
+      cappedBoostsPda = findPda([
+        Buffer.from("boosts"),
+        cappedConfigPda.toBuffer(),
+      ]);
+      cappedStakePda = findPda([
+        Buffer.from("stake"),
+        cappedConfigPda.toBuffer(),
+        cappedAsset.publicKey.toBuffer(),
+      ]);
+
+      await program.methods
+        .createCollection({
+          name: "Capped Pool",
+          uri: "https://example.com/capped.json",
+          nftName: "Capped NFT",
+          nftUri: "https://example.com/capped-nft.json",
+          maxSupply: 1,
+          priceLamports: new anchor.BN(0),
+        })
+        .accountsStrict({
+          authority: admin.publicKey,
+          collection: capped.publicKey,
+          collectionInfo: cappedInfoPda,
+          treasury: cappedTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([capped])
+        .rpc();
+      await program.methods
+        .initializeConfig(
+          255,
+          cappedRateBps,
+          maxStake,
+          [{ lockDays: 0, multiplierBps: 65_535 }],
+          { maxSupply: cappedSupply, epochDuration: new anchor.BN(30 * 86_400) }
+        )
+        .accountsStrict({
+          admin: admin.publicKey,
+          collectionInfo: cappedInfoPda,
+          config: cappedConfigPda,
+          rewardMint: cappedMintPda,
+          tokenProgram: anchor.utils.token.TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+          thisProgram: program.programId,
+          programData: programDataAccount,
+        })
+        .rpc();
+      await program.methods
+        .initializeUser()
+        .accountsStrict({
+          user: user.publicKey,
+          config: cappedConfigPda,
+          userAccount: cappedUserPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+      await program.methods
+        .setTraitBoosts([
+          {
+            key: "Minter",
+            value: user.publicKey.toString(),
+            multiplierBps: 65_535,
+          },
+        ])
+        .accountsStrict({
+          admin: admin.publicKey,
+          config: cappedConfigPda,
+          boostTable: cappedBoostsPda,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+      await program.methods
+        .mintNft()
+        .accountsStrict({
+          minter: user.publicKey,
+          asset: cappedAsset.publicKey,
+          collection: capped.publicKey,
+          collectionInfo: cappedInfoPda,
+          treasury: cappedTreasuryPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user, cappedAsset])
+        .rpc();
+      await program.methods
+        .stake(0)
+        .accountsStrict({
+          user: user.publicKey,
+          asset: cappedAsset.publicKey,
+          collection: capped.publicKey,
+          collectionInfo: cappedInfoPda,
+          stakeAccount: cappedStakePda,
+          boostTable: cappedBoostsPda,
+          config: cappedConfigPda,
+          userAccount: cappedUserPda,
+          coreProgram: MPL_CORE_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([user])
+        .rpc();
+    });
+
+    it("Rejects minting past the collection's max supply", async () => {
+      const extra = Keypair.generate();
+      try {
+        await program.methods
+          .mintNft()
+          .accountsStrict({
+            minter: user.publicKey,
+            asset: extra.publicKey,
+            collection: capped.publicKey,
+            collectionInfo: cappedInfoPda,
+            treasury: cappedTreasuryPda,
+            coreProgram: MPL_CORE_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([user, extra])
+          .rpc();
+        assert.fail("Should have thrown MaxSupplyReached");
+      } catch (err) {
+        assert.include(err.toString(), "MaxSupplyReached");
+      }
+
+      const info = await program.account.collectionInfo.fetch(cappedInfoPda);
+      assert.equal(info.minted, 1);
+    });
+
+    it("Keeps the mint authority until the cap is reached", async () => {
+      try {
+        await releaseMintAuthority();
+        assert.fail("Should have thrown EmissionsNotEnded");
+      } catch (err) {
+        assert.include(err.toString(), "EmissionsNotEnded");
+      }
+    });
+
+    it("Claim up to the cap", async () => {
+      // Long enough to earn at least one whole point
+      await sleep(10_000);
+
+      const tx = await claim();
+      console.log(`Claim tx: ${tx}`);
+
+      const config = await program.account.stakeConfig.fetch(cappedConfigPda);
+      assert.ok(config.emissions.minted.eq(cappedSupply));
+      const rewards = await connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(cappedMintPda, user.publicKey)
+      );
+      assert.equal(rewards.value.amount, cappedSupply.toString());
+    });
+
+    it("Rejects claims past the cap", async () => {
+      // More points, nothing left to pay them with
+      await sleep(10_000);
+
+      try {
+        await claim();
+        assert.fail("Should have thrown EmissionsEnded");
+      } catch (err) {
+        assert.include(err.toString(), "EmissionsEnded");
+      }
+    });
+
+    it("Release the mint authority once the cap is reached", async () => {
+      const tx = await releaseMintAuthority();
+      console.log(`Release Mint Authority tx: ${tx}`);
+
+      const mint = await getMint(connection, cappedMintPda);
+      assert.equal(
+        mint.mintAuthority.toString(),
+        daoAuthority.publicKey.toString()
+      );
+    });
+  });
 });